@@ -1,52 +1,188 @@
 use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt::{Display, Formatter};
 
-pub fn part_1(blocks: &[Vec<i32>]) -> (usize, i32) {
-    let max_element_and_index = blocks
-        .iter()
-        .map(|part| part.iter().sum::<i32>())
-        .enumerate()
-        .max_by(|(_, a), (_, b)| a.cmp(b));
+pub mod partition;
+pub mod stats;
+
+/// Ranks the elves by the calories they carry and returns the `n` best ones as
+/// `(elf index, total)` pairs, highest total first. Ties are broken by the lower elf index.
+pub fn top_n(blocks: &[Vec<u64>], n: usize) -> Vec<(usize, u64)> {
+    rank_totals(blocks.iter().map(|part| part.iter().sum::<u64>()), n)
+}
+
+/// Streams the elf totals through a min-heap holding at most `n` entries, so ranking runs in
+/// `O(elves * log n)` and never needs the full list of totals in memory.
+fn rank_totals(totals: impl IntoIterator<Item = u64>, n: usize) -> Vec<(usize, u64)> {
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let mut heap = BinaryHeap::with_capacity(n + 1);
+    for (index, total) in totals.into_iter().enumerate() {
+        heap.push(Reverse((total, Reverse(index))));
+        if heap.len() > n {
+            heap.pop();
+        }
+    }
+
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((total, Reverse(index)))| (index, total))
+        .collect()
+}
+
+/// The elf carrying the most calories, or `None` if there are no elves.
+pub fn part_1(blocks: &[Vec<u64>]) -> Option<(usize, u64)> {
+    top_n(blocks, 1).first().copied()
+}
+
+pub fn part_2(blocks: &[Vec<u64>]) -> u64 {
+    top_n(blocks, 3).iter().map(|(_, total)| total).sum()
+}
 
-    max_element_and_index.unwrap()
+#[derive(Clone, PartialEq, Debug)]
+pub struct Elf {
+    pub index: usize,
+    pub items: Vec<u64>,
 }
 
-pub fn part_2(blocks: &[Vec<i32>]) -> i32 {
-    let mut summed_blocks = blocks
-        .iter()
-        .map(|part| part.iter().sum())
-        .collect::<Vec<i32>>();
+impl Elf {
+    pub fn total(&self) -> u64 {
+        self.items.iter().sum()
+    }
+
+    pub fn item_count(&self) -> usize {
+        self.items.len()
+    }
+}
 
-    summed_blocks.sort_by_key(|b| Reverse(*b));
-    let calories = summed_blocks[..3].iter().sum::<i32>();
+pub fn into_elves(blocks: Vec<Vec<u64>>) -> Vec<Elf> {
+    blocks
+        .into_iter()
+        .enumerate()
+        .map(|(index, items)| Elf { index, items })
+        .collect()
+}
 
-    calories
+#[derive(Clone, PartialEq, Debug)]
+pub struct ParseCaloriesError {
+    pub line: usize,
+    pub content: String,
 }
 
-fn split_string(input: &str) -> Vec<Vec<i32>> {
-    input
-        .split("\n\n")
-        .map(|block| {
-            block
-                .split('\n')
-                .map(|part| part.parse::<i32>().unwrap_or(0))
-                .collect::<Vec<i32>>()
-        })
-        .collect::<Vec<Vec<i32>>>()
+impl Display for ParseCaloriesError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}: {:?} is not a calorie count",
+            self.line, self.content
+        )
+    }
+}
+
+/// Parses the calorie list into one block of items per elf. Any run of blank lines separates two
+/// elves, CRLF line endings and surrounding whitespace are ignored, and every other line has to
+/// be a non-negative number.
+pub fn split_string(input: &str) -> Result<Vec<Vec<u64>>, ParseCaloriesError> {
+    let mut blocks = Vec::new();
+    let mut current = Vec::new();
+
+    for (index, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            if !current.is_empty() {
+                blocks.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+
+        current.push(line.parse::<u64>().map_err(|_| ParseCaloriesError {
+            line: index + 1,
+            content: line.to_string(),
+        })?);
+    }
+
+    if !current.is_empty() {
+        blocks.push(current);
+    }
+
+    Ok(blocks)
 }
 
 #[cfg(test)]
 mod test {
-    use crate::day1::{part_1, part_2, split_string};
+    use crate::day1::{part_1, part_2, split_string, top_n, ParseCaloriesError};
 
     const INPUT: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n\n10000";
 
     #[test]
     fn test_part_1() {
-        assert_eq!((3, 24000i32), part_1(&split_string(INPUT)));
+        assert_eq!(Some((3, 24000u64)), part_1(&split_string(INPUT).unwrap()));
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(45000, part_2(&split_string(INPUT)));
+        assert_eq!(45000, part_2(&split_string(INPUT).unwrap()));
+    }
+
+    #[test]
+    fn test_top_n() {
+        let blocks = split_string(INPUT).unwrap();
+
+        assert_eq!(top_n(&blocks, 2), vec![(3, 24000), (2, 11000)]);
+        assert_eq!(top_n(&blocks, 0), vec![]);
+        assert_eq!(top_n(&blocks, 10).len(), blocks.len());
+    }
+
+    #[test]
+    fn test_top_n_with_few_elves() {
+        let blocks = vec![vec![100, 200], vec![300]];
+
+        assert_eq!(top_n(&blocks, 3), vec![(0, 300), (1, 300)]);
+        assert_eq!(part_2(&blocks), 600);
+    }
+
+    #[test]
+    fn test_no_elves() {
+        for input in ["", "\n \n\r\n"] {
+            let blocks = split_string(input).unwrap();
+
+            assert_eq!(part_1(&blocks), None);
+            assert_eq!(part_2(&blocks), 0);
+        }
+    }
+
+    #[test]
+    fn test_split_string() {
+        let expected = vec![
+            vec![1000, 2000, 3000],
+            vec![4000],
+            vec![5000, 6000],
+            vec![7000, 8000, 9000],
+            vec![10000],
+        ];
+
+        assert_eq!(split_string(INPUT).unwrap(), expected);
+        assert_eq!(
+            split_string("\n\n1000\r\n2000  \r\n\r\n \r\n3000\r\n\n").unwrap(),
+            vec![vec![1000, 2000], vec![3000]]
+        );
+        assert_eq!(
+            split_string("5000000000\n5000000000").unwrap(),
+            vec![vec![5000000000, 5000000000]]
+        );
+    }
+
+    #[test]
+    fn test_split_string_rejects_garbage() {
+        assert_eq!(
+            split_string("1000\n\n12a00\n3000"),
+            Err(ParseCaloriesError {
+                line: 3,
+                content: "12a00".to_string()
+            })
+        );
+        assert!(split_string("1000\n-5").is_err());
     }
 }
@@ -0,0 +1,386 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt::{Display, Formatter};
+
+/// Above this many food items the exact search is not even attempted.
+const EXACT_ITEM_LIMIT: usize = 24;
+
+/// How many partial assignments the exact search may visit before it settles for the best
+/// assignment found so far, which keeps it to a fraction of a second on any input.
+const NODE_BUDGET: usize = 200_000;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Solver {
+    BranchAndBound,
+    Greedy,
+    KarmarkarKarp,
+}
+
+/// A single food item, remembering which elf originally carried it.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct FoodItem {
+    pub elf: usize,
+    pub position: usize,
+    pub calories: u64,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Redistribution {
+    pub solver: Solver,
+    /// The food items every elf carries after the redistribution.
+    pub assignment: Vec<Vec<FoodItem>>,
+    pub loads: Vec<u64>,
+    pub makespan: u64,
+    /// No assignment can do better than the larger of the biggest item and the average load.
+    pub lower_bound: u64,
+}
+
+impl Redistribution {
+    /// How many calories the largest load is above the lower bound.
+    pub fn gap(&self) -> u64 {
+        self.makespan - self.lower_bound
+    }
+
+    pub fn gap_percent(&self) -> f64 {
+        if self.lower_bound == 0 {
+            return 0.0;
+        }
+
+        self.gap() as f64 * 100.0 / self.lower_bound as f64
+    }
+}
+
+impl Display for Redistribution {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (elf, (items, load)) in self.assignment.iter().zip(&self.loads).enumerate() {
+            let items = items
+                .iter()
+                .map(|item| item.calories.to_string())
+                .collect::<Vec<_>>()
+                .join(" + ");
+
+            writeln!(f, "elf {}: {load} = {items}", elf + 1)?;
+        }
+
+        writeln!(
+            f,
+            "largest load {} with {:?}, lower bound {}, gap {} ({:.2}%)",
+            self.makespan,
+            self.solver,
+            self.lower_bound,
+            self.gap(),
+            self.gap_percent()
+        )
+    }
+}
+
+fn lower_bound(items: &[u64], k: usize) -> u64 {
+    let total = items.iter().sum::<u64>();
+    let largest = items.iter().copied().max().unwrap_or(0);
+
+    total.div_ceil(k as u64).max(largest)
+}
+
+fn makespan(items: &[u64], bins: &[Vec<usize>]) -> u64 {
+    bins.iter()
+        .map(|bin| bin.iter().map(|item| items[*item]).sum())
+        .max()
+        .unwrap_or(0)
+}
+
+fn descending_order(items: &[u64]) -> Vec<usize> {
+    let mut order = (0..items.len()).collect::<Vec<_>>();
+    order.sort_by_key(|item| Reverse(items[*item]));
+
+    order
+}
+
+/// Longest-processing-time-first: hand out the largest items first, always to the elf with the
+/// lightest load so far.
+pub fn greedy(items: &[u64], k: usize) -> Vec<Vec<usize>> {
+    let mut bins = vec![Vec::new(); k];
+    let mut loads = (0..k)
+        .map(|bin| Reverse((0, bin)))
+        .collect::<BinaryHeap<_>>();
+
+    for item in descending_order(items) {
+        let Reverse((load, bin)) = loads.pop().unwrap();
+        bins[bin].push(item);
+        loads.push(Reverse((load + items[item], bin)));
+    }
+
+    bins
+}
+
+/// Multiway Karmarkar-Karp differencing. Every item starts out as its own k-way partial
+/// partition; the two partials with the largest spread are repeatedly merged by pairing the
+/// heaviest subset of one with the lightest subset of the other.
+pub fn karmarkar_karp(items: &[u64], k: usize) -> Vec<Vec<usize>> {
+    if items.is_empty() {
+        return vec![Vec::new(); k];
+    }
+
+    // Each partial keeps its subsets sorted by descending sum.
+    let mut partials = items
+        .iter()
+        .enumerate()
+        .map(|(item, calories)| {
+            let mut partial = vec![(0, Vec::new()); k];
+            partial[0] = (*calories, vec![item]);
+            partial
+        })
+        .collect::<Vec<_>>();
+    let spread = |partial: &[(u64, Vec<usize>)]| partial[0].0 - partial[k - 1].0;
+
+    let mut heap = partials
+        .iter()
+        .enumerate()
+        .map(|(index, partial)| (spread(partial), Reverse(index)))
+        .collect::<BinaryHeap<_>>();
+
+    while heap.len() > 1 {
+        let (_, Reverse(first)) = heap.pop().unwrap();
+        let (_, Reverse(second)) = heap.pop().unwrap();
+
+        let second = std::mem::take(&mut partials[second]);
+        let mut merged = std::mem::take(&mut partials[first])
+            .into_iter()
+            .zip(second.into_iter().rev())
+            .map(|((sum_a, mut subset_a), (sum_b, subset_b))| {
+                subset_a.extend(subset_b);
+                (sum_a + sum_b, subset_a)
+            })
+            .collect::<Vec<_>>();
+        merged.sort_by_key(|(sum, _)| Reverse(*sum));
+
+        heap.push((spread(&merged), Reverse(partials.len())));
+        partials.push(merged);
+    }
+
+    let (_, Reverse(last)) = heap.pop().unwrap();
+    std::mem::take(&mut partials[last])
+        .into_iter()
+        .map(|(_, subset)| subset)
+        .collect()
+}
+
+struct Search<'a> {
+    items: &'a [u64],
+    order: Vec<usize>,
+    lower_bound: u64,
+    loads: Vec<u64>,
+    bins: Vec<Vec<usize>>,
+    best: u64,
+    best_bins: Vec<Vec<usize>>,
+    nodes_left: usize,
+}
+
+impl Search<'_> {
+    fn run(&mut self, depth: usize) {
+        if self.best == self.lower_bound || self.nodes_left == 0 {
+            return;
+        }
+        self.nodes_left -= 1;
+
+        if depth == self.order.len() {
+            let makespan = self.loads.iter().copied().max().unwrap_or(0);
+            if makespan < self.best {
+                self.best = makespan;
+                self.best_bins = self.bins.clone();
+            }
+            return;
+        }
+
+        let item = self.order[depth];
+        let calories = self.items[item];
+        let mut tried_loads = Vec::new();
+
+        for bin in 0..self.loads.len() {
+            let load = self.loads[bin];
+
+            // Bins with the same load are interchangeable, and a bin that would reach the best
+            // makespan found so far cannot lead to an improvement.
+            if tried_loads.contains(&load) || load + calories >= self.best {
+                continue;
+            }
+            tried_loads.push(load);
+
+            self.loads[bin] += calories;
+            self.bins[bin].push(item);
+            self.run(depth + 1);
+            self.bins[bin].pop();
+            self.loads[bin] -= calories;
+        }
+    }
+}
+
+/// Branch-and-bound search starting from the given assignment. Returns the best assignment found
+/// and whether it is proven optimal, which it is unless the node budget ran out first.
+fn search(items: &[u64], k: usize, initial: Vec<Vec<usize>>) -> (Vec<Vec<usize>>, bool) {
+    let mut search = Search {
+        items,
+        order: descending_order(items),
+        lower_bound: lower_bound(items, k),
+        loads: vec![0; k],
+        bins: vec![Vec::new(); k],
+        best: makespan(items, &initial),
+        best_bins: initial,
+        nodes_left: NODE_BUDGET,
+    };
+
+    search.run(0);
+    let optimal = search.nodes_left > 0 || search.best == search.lower_bound;
+    (search.best_bins, optimal)
+}
+
+/// Branch-and-bound search, seeded with the greedy solution as the initial upper bound. It runs
+/// in exponential time, so it gives up after a fixed number of nodes and returns the best
+/// assignment found by then, which is optimal for small inputs only.
+pub fn branch_and_bound(items: &[u64], k: usize) -> Vec<Vec<usize>> {
+    search(items, k, greedy(items, k)).0
+}
+
+/// Redistributes all food items among `k` elves so that the largest calorie load is as small as
+/// possible. Small inputs are solved exactly as far as the node budget allows, larger ones with
+/// the better of the two heuristics.
+pub fn redistribute(blocks: &[Vec<u64>], k: usize) -> Option<Redistribution> {
+    if k == 0 {
+        return None;
+    }
+
+    let food = blocks
+        .iter()
+        .enumerate()
+        .flat_map(|(elf, items)| {
+            items
+                .iter()
+                .enumerate()
+                .map(move |(position, calories)| FoodItem {
+                    elf,
+                    position,
+                    calories: *calories,
+                })
+        })
+        .collect::<Vec<_>>();
+    let items = food.iter().map(|item| item.calories).collect::<Vec<_>>();
+
+    let greedy = greedy(&items, k);
+    let karmarkar_karp = karmarkar_karp(&items, k);
+    let (heuristic, initial) = if makespan(&items, &karmarkar_karp) < makespan(&items, &greedy) {
+        (Solver::KarmarkarKarp, karmarkar_karp)
+    } else {
+        (Solver::Greedy, greedy)
+    };
+
+    // The exact search starts from the better heuristic, so running out of nodes still leaves
+    // that result or an improvement on it.
+    let (solver, bins) = if items.len() <= EXACT_ITEM_LIMIT {
+        let heuristic_makespan = makespan(&items, &initial);
+        let (bins, optimal) = search(&items, k, initial);
+
+        if optimal || makespan(&items, &bins) < heuristic_makespan {
+            (Solver::BranchAndBound, bins)
+        } else {
+            (heuristic, bins)
+        }
+    } else {
+        (heuristic, initial)
+    };
+
+    Some(Redistribution {
+        solver,
+        loads: bins
+            .iter()
+            .map(|bin| bin.iter().map(|item| items[*item]).sum())
+            .collect(),
+        makespan: makespan(&items, &bins),
+        lower_bound: lower_bound(&items, k),
+        assignment: bins
+            .into_iter()
+            .map(|bin| bin.into_iter().map(|item| food[item]).collect())
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::day1::partition::{
+        branch_and_bound, greedy, karmarkar_karp, makespan, redistribute, Solver,
+    };
+    use crate::day1::split_string;
+
+    const INPUT: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n\n10000";
+
+    fn assert_is_partition(bins: &[Vec<usize>], item_count: usize) {
+        let mut items = bins.concat();
+        items.sort_unstable();
+
+        assert_eq!(items, (0..item_count).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_redistribute() {
+        let redistribution = redistribute(&split_string(INPUT).unwrap(), 3).unwrap();
+
+        assert_eq!(redistribution.solver, Solver::BranchAndBound);
+        assert_eq!(redistribution.makespan, 19000);
+        assert_eq!(redistribution.lower_bound, 18334);
+        assert_eq!(redistribution.gap(), 666);
+        assert_eq!(redistribution.loads.iter().sum::<u64>(), 55000);
+        assert_eq!(redistribution.assignment.concat().len(), 10);
+        assert_eq!(redistribute(&[vec![1]], 0), None);
+    }
+
+    #[test]
+    fn test_branch_and_bound_beats_greedy() {
+        let items = [3, 3, 2, 2, 2];
+
+        assert_eq!(makespan(&items, &greedy(&items, 2)), 7);
+        assert_eq!(makespan(&items, &branch_and_bound(&items, 2)), 6);
+        assert_is_partition(&branch_and_bound(&items, 2), items.len());
+    }
+
+    #[test]
+    fn test_karmarkar_karp() {
+        let items = [8, 7, 6, 5, 4];
+        let bins = karmarkar_karp(&items, 2);
+
+        assert_is_partition(&bins, items.len());
+        assert_eq!(bins.len(), 2);
+        assert_eq!(makespan(&items, &bins), 16);
+
+        let items = (1..=100).map(|i| i * 37 % 101).collect::<Vec<u64>>();
+        for k in [2, 3, 7] {
+            let bins = karmarkar_karp(&items, k);
+
+            assert_is_partition(&bins, items.len());
+            assert!(makespan(&items, &bins) <= makespan(&items, &greedy(&items, k)) + 101);
+        }
+    }
+
+    #[test]
+    fn test_more_elves_than_items() {
+        let redistribution = redistribute(&[vec![5, 3]], 4).unwrap();
+
+        assert_eq!(redistribution.makespan, 5);
+        assert_eq!(redistribution.gap(), 0);
+        assert_eq!(redistribution.loads.len(), 4);
+    }
+
+    #[test]
+    fn test_node_budget() {
+        let items = (1..=24u64)
+            .map(|i| 100_000 + i * 7_919 * 7_919 % 900_000)
+            .collect::<Vec<_>>();
+        let blocks = items.iter().map(|item| vec![*item]).collect::<Vec<_>>();
+
+        for k in [3, 5] {
+            let redistribution = redistribute(&blocks, k).unwrap();
+            let heuristic = makespan(&items, &greedy(&items, k))
+                .min(makespan(&items, &karmarkar_karp(&items, k)));
+
+            assert!(redistribution.makespan <= heuristic);
+            assert_is_partition(&branch_and_bound(&items, k), items.len());
+        }
+    }
+}
@@ -0,0 +1,280 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crate::day1::Elf;
+
+const PERCENTILES: [u8; 5] = [10, 25, 75, 90, 99];
+const HISTOGRAM_BUCKETS: u64 = 10;
+const HISTOGRAM_WIDTH: usize = 40;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum OutputFormat {
+    Table,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "table" => Ok(Self::Table),
+            "json" => Ok(Self::Json),
+            _ => Err(format!(
+                "unknown output format {string:?}, expected table or json"
+            )),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct HistogramBucket {
+    pub start: u64,
+    pub end: u64,
+    pub count: usize,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct InventoryStats {
+    pub count: usize,
+    pub total: u64,
+    pub min: u64,
+    pub max: u64,
+    pub mean: f64,
+    pub median: f64,
+    /// `(percentile, calories)` pairs using the nearest-rank method.
+    pub percentiles: Vec<(u8, u64)>,
+    /// Maps the number of items an elf carries to the number of elves carrying that many.
+    pub item_counts: BTreeMap<usize, usize>,
+    /// Equal-width buckets of calorie totals, both bounds inclusive.
+    pub histogram: Vec<HistogramBucket>,
+}
+
+/// Nearest-rank percentile of an ascending list of totals.
+fn percentile(sorted: &[u64], percentile: u8) -> u64 {
+    let rank = (percentile as usize * sorted.len()).div_ceil(100);
+
+    sorted[rank.saturating_sub(1)]
+}
+
+fn histogram(sorted: &[u64]) -> Vec<HistogramBucket> {
+    let (min, max) = (sorted[0], sorted[sorted.len() - 1]);
+    let width = ((max - min) / HISTOGRAM_BUCKETS + 1).max(1);
+
+    let mut buckets = (0..HISTOGRAM_BUCKETS)
+        .map(|bucket| min + bucket * width)
+        .take_while(|start| *start <= max)
+        .map(|start| HistogramBucket {
+            start,
+            end: start + width - 1,
+            count: 0,
+        })
+        .collect::<Vec<_>>();
+
+    for total in sorted {
+        buckets[((total - min) / width) as usize].count += 1;
+    }
+
+    buckets
+}
+
+/// Summarises the calorie totals of all elves, or returns `None` if there are no elves at all.
+pub fn stats(elves: &[Elf]) -> Option<InventoryStats> {
+    if elves.is_empty() {
+        return None;
+    }
+
+    let mut totals = elves.iter().map(Elf::total).collect::<Vec<_>>();
+    totals.sort_unstable();
+
+    let count = totals.len();
+    let total = totals.iter().sum::<u64>();
+    let median = if count % 2 == 0 {
+        (totals[count / 2 - 1] + totals[count / 2]) as f64 / 2.0
+    } else {
+        totals[count / 2] as f64
+    };
+
+    let mut item_counts = BTreeMap::new();
+    for elf in elves {
+        *item_counts.entry(elf.item_count()).or_insert(0) += 1;
+    }
+
+    Some(InventoryStats {
+        count,
+        total,
+        min: totals[0],
+        max: totals[count - 1],
+        mean: total as f64 / count as f64,
+        median,
+        percentiles: PERCENTILES
+            .iter()
+            .map(|p| (*p, percentile(&totals, *p)))
+            .collect(),
+        item_counts,
+        histogram: histogram(&totals),
+    })
+}
+
+impl InventoryStats {
+    pub fn render(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Table => self.to_table(),
+            OutputFormat::Json => self.to_json(),
+        }
+    }
+
+    pub fn to_table(&self) -> String {
+        let mut rows = vec![
+            ("elves".to_string(), self.count.to_string()),
+            ("total".to_string(), self.total.to_string()),
+            ("min".to_string(), self.min.to_string()),
+            ("max".to_string(), self.max.to_string()),
+            ("mean".to_string(), format!("{:.2}", self.mean)),
+            ("median".to_string(), format!("{:.1}", self.median)),
+        ];
+        rows.extend(
+            self.percentiles
+                .iter()
+                .map(|(p, calories)| (format!("p{p}"), calories.to_string())),
+        );
+        rows.extend(
+            self.item_counts
+                .iter()
+                .map(|(items, elves)| (format!("elves with {items} items"), elves.to_string())),
+        );
+
+        let label_width = rows.iter().map(|(label, _)| label.len()).max().unwrap();
+        let mut table = rows
+            .iter()
+            .map(|(label, value)| format!("{label:<label_width$}  {value:>12}\n"))
+            .collect::<String>();
+
+        table.push('\n');
+        table.push_str(&self.histogram_text());
+        table
+    }
+
+    /// Renders the histogram as one bar per bucket, scaled to the fullest bucket.
+    pub fn histogram_text(&self) -> String {
+        let fullest = self.histogram.iter().map(|b| b.count).max().unwrap_or(0);
+        let range_width = self
+            .histogram
+            .iter()
+            .map(|b| format!("{}-{}", b.start, b.end).len())
+            .max()
+            .unwrap_or(0);
+
+        self.histogram
+            .iter()
+            .map(|bucket| {
+                let bar = "#".repeat(bucket.count * HISTOGRAM_WIDTH / fullest.max(1));
+                let range = format!("{}-{}", bucket.start, bucket.end);
+
+                format!("{range:>range_width$} | {bar} {}\n", bucket.count)
+            })
+            .collect()
+    }
+
+    pub fn to_json(&self) -> String {
+        let percentiles = self
+            .percentiles
+            .iter()
+            .map(|(p, calories)| format!("\"p{p}\":{calories}"))
+            .collect::<Vec<_>>()
+            .join(",");
+        let item_counts = self
+            .item_counts
+            .iter()
+            .map(|(items, elves)| format!("\"{items}\":{elves}"))
+            .collect::<Vec<_>>()
+            .join(",");
+        let histogram = self
+            .histogram
+            .iter()
+            .map(|b| {
+                format!(
+                    "{{\"start\":{},\"end\":{},\"count\":{}}}",
+                    b.start, b.end, b.count
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        format!(
+            "{{\"count\":{},\"total\":{},\"min\":{},\"max\":{},\"mean\":{},\"median\":{},\
+             \"percentiles\":{{{percentiles}}},\"item_counts\":{{{item_counts}}},\
+             \"histogram\":[{histogram}]}}\n",
+            self.count, self.total, self.min, self.max, self.mean, self.median,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::day1::stats::{stats, HistogramBucket};
+    use crate::day1::{into_elves, split_string};
+
+    const INPUT: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n\n10000";
+
+    #[test]
+    fn test_stats() {
+        let stats = stats(&into_elves(split_string(INPUT).unwrap())).unwrap();
+
+        assert_eq!(stats.count, 5);
+        assert_eq!(stats.total, 55000);
+        assert_eq!((stats.min, stats.max), (4000, 24000));
+        assert_eq!(stats.mean, 11000.0);
+        assert_eq!(stats.median, 10000.0);
+        assert_eq!(
+            stats.percentiles,
+            vec![
+                (10, 4000),
+                (25, 6000),
+                (75, 11000),
+                (90, 24000),
+                (99, 24000)
+            ]
+        );
+        assert_eq!(
+            stats.item_counts.into_iter().collect::<Vec<_>>(),
+            vec![(1, 2), (2, 1), (3, 2)]
+        );
+        assert_eq!(stats.histogram.len(), 10);
+        assert_eq!(
+            stats.histogram[0],
+            HistogramBucket {
+                start: 4000,
+                end: 6000,
+                count: 2
+            }
+        );
+        assert_eq!(stats.histogram.iter().map(|b| b.count).sum::<usize>(), 5);
+    }
+
+    #[test]
+    fn test_stats_without_elves() {
+        assert_eq!(stats(&[]), None);
+    }
+
+    #[test]
+    fn test_render() {
+        let stats = stats(&into_elves(split_string("100\n\n300").unwrap())).unwrap();
+
+        assert_eq!(
+            stats.to_json(),
+            "{\"count\":2,\"total\":400,\"min\":100,\"max\":300,\"mean\":200,\"median\":200,\
+             \"percentiles\":{\"p10\":100,\"p25\":100,\"p75\":300,\"p90\":300,\"p99\":300},\
+             \"item_counts\":{\"1\":2},\"histogram\":[{\"start\":100,\"end\":120,\"count\":1},\
+             {\"start\":121,\"end\":141,\"count\":0},{\"start\":142,\"end\":162,\"count\":0},\
+             {\"start\":163,\"end\":183,\"count\":0},{\"start\":184,\"end\":204,\"count\":0},\
+             {\"start\":205,\"end\":225,\"count\":0},{\"start\":226,\"end\":246,\"count\":0},\
+             {\"start\":247,\"end\":267,\"count\":0},{\"start\":268,\"end\":288,\"count\":0},\
+             {\"start\":289,\"end\":309,\"count\":1}]}\n"
+        );
+        assert!(stats.to_table().starts_with("elves  "));
+        assert!(stats
+            .histogram_text()
+            .starts_with("100-120 | ######################################## 1\n"));
+    }
+}
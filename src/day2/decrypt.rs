@@ -0,0 +1,192 @@
+use std::cmp::Reverse;
+use std::fmt::{Display, Formatter};
+
+use crate::day2::encoding::{Decode, Encoding, EncodingError};
+use crate::day2::{parse_input, Outcome, Shape};
+
+const SYMBOLS: [&str; 3] = ["X", "Y", "Z"];
+
+/// A symbol of the second, encrypted column of the strategy guide.
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct Symbol(usize);
+
+impl Decode for Symbol {
+    /// Symbols are read the same way under every encoding.
+    fn decode(_: &Encoding, token: &str) -> Option<Self> {
+        SYMBOLS.iter().position(|symbol| *symbol == token).map(Self)
+    }
+}
+
+/// One possible reading of the `X`/`Y`/`Z` column, indexed by symbol.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Interpretation {
+    Shapes([Shape; 3]),
+    Outcomes([Outcome; 3]),
+}
+
+impl Interpretation {
+    /// The shape we play in a round against the given opponent shape.
+    fn our_shape(&self, opponent: Shape, symbol: usize) -> Shape {
+        match self {
+            Self::Shapes(shapes) => shapes[symbol],
+            Self::Outcomes(outcomes) => opponent.get_shape_for_desired_outcome(outcomes[symbol]),
+        }
+    }
+}
+
+impl Display for Interpretation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let meanings = match self {
+            Self::Shapes(shapes) => shapes.map(|shape| format!("{shape:?}")),
+            Self::Outcomes(outcomes) => outcomes.map(|outcome| format!("{outcome:?}")),
+        };
+
+        let mapping = SYMBOLS
+            .iter()
+            .zip(meanings)
+            .map(|(symbol, meaning)| format!("{symbol}={meaning}"))
+            .collect::<Vec<_>>();
+
+        write!(f, "{}", mapping.join(" "))
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Evaluation {
+    pub interpretation: Interpretation,
+    pub score: u32,
+}
+
+/// The score of every interpretation of a strategy guide, best first.
+#[derive(Clone, PartialEq, Debug)]
+pub struct DecryptionReport {
+    pub evaluations: Vec<Evaluation>,
+}
+
+impl DecryptionReport {
+    pub fn best(&self) -> &Evaluation {
+        &self.evaluations[0]
+    }
+
+    pub fn worst(&self) -> &Evaluation {
+        &self.evaluations[self.evaluations.len() - 1]
+    }
+
+    /// All interpretations that reproduce the given total score.
+    pub fn matching(&self, target: u32) -> Vec<&Evaluation> {
+        self.evaluations
+            .iter()
+            .filter(|evaluation| evaluation.score == target)
+            .collect()
+    }
+}
+
+impl Display for DecryptionReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for evaluation in &self.evaluations {
+            writeln!(f, "{:>8}  {}", evaluation.score, evaluation.interpretation)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn permutations<T: Copy>([a, b, c]: [T; 3]) -> [[T; 3]; 6] {
+    [
+        [a, b, c],
+        [a, c, b],
+        [b, a, c],
+        [b, c, a],
+        [c, a, b],
+        [c, b, a],
+    ]
+}
+
+fn interpretations() -> Vec<Interpretation> {
+    let shapes = permutations([Shape::Rock, Shape::Paper, Shape::Scissors]);
+    let outcomes = permutations([Outcome::Loose, Outcome::Draw, Outcome::Win]);
+
+    shapes
+        .into_iter()
+        .map(Interpretation::Shapes)
+        .chain(outcomes.into_iter().map(Interpretation::Outcomes))
+        .collect()
+}
+
+/// Scores the strategy guide under every permutation of the `X`/`Y`/`Z` column, both read as
+/// shapes and read as outcomes. The guide is reduced to counts of its nine distinct rounds first,
+/// so each interpretation is scored in constant time.
+pub fn decrypt(input: &str) -> Result<DecryptionReport, EncodingError> {
+    let mut counts = [[0u32; 3]; 3];
+    for (opponent, Symbol(symbol)) in parse_input::<Shape, Symbol>(input)? {
+        counts[opponent.index()][symbol] += 1;
+    }
+
+    let mut evaluations = interpretations()
+        .into_iter()
+        .map(|interpretation| Evaluation {
+            interpretation,
+            score: (0..3)
+                .flat_map(|opponent| (0..3).map(move |symbol| (opponent, symbol)))
+                .map(|(opponent, symbol)| {
+                    let opponent_shape = Shape::from_index(opponent);
+                    let our_shape = interpretation.our_shape(opponent_shape, symbol);
+
+                    counts[opponent][symbol] * our_shape.play_against(opponent_shape) as u32
+                })
+                .sum(),
+        })
+        .collect::<Vec<_>>();
+    evaluations.sort_by_key(|evaluation| Reverse(evaluation.score));
+
+    Ok(DecryptionReport { evaluations })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::day2::decrypt::{decrypt, Interpretation};
+    use crate::day2::{Outcome, Shape};
+
+    const EXAMPLE_INPUT: &str = "A Y\nB X\nC Z\n";
+    const FILE_INPUT: &str = include_str!("input.txt");
+
+    const DEFAULT_SHAPES: Interpretation =
+        Interpretation::Shapes([Shape::Rock, Shape::Paper, Shape::Scissors]);
+    const DEFAULT_OUTCOMES: Interpretation =
+        Interpretation::Outcomes([Outcome::Loose, Outcome::Draw, Outcome::Win]);
+
+    #[test]
+    fn test_decrypt() {
+        let report = decrypt(EXAMPLE_INPUT).unwrap();
+
+        assert_eq!(report.evaluations.len(), 12);
+        assert!(report
+            .matching(15)
+            .iter()
+            .any(|evaluation| evaluation.interpretation == DEFAULT_SHAPES));
+        assert!(report
+            .matching(12)
+            .iter()
+            .any(|evaluation| evaluation.interpretation == DEFAULT_OUTCOMES));
+        assert!(report.best().score >= 15);
+        assert!(report.worst().score <= 12);
+    }
+
+    #[test]
+    fn test_decrypt_file_input() {
+        let report = decrypt(FILE_INPUT).unwrap();
+
+        assert_eq!(report.matching(14297)[0].interpretation, DEFAULT_SHAPES);
+        assert_eq!(report.matching(10498)[0].interpretation, DEFAULT_OUTCOMES);
+        assert!(report
+            .evaluations
+            .windows(2)
+            .all(|pair| pair[0].score >= pair[1].score));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(DEFAULT_SHAPES.to_string(), "X=Rock Y=Paper Z=Scissors");
+        assert_eq!(DEFAULT_OUTCOMES.to_string(), "X=Loose Y=Draw Z=Win");
+    }
+}
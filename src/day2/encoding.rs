@@ -0,0 +1,284 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use crate::day2::{Outcome, Shape};
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum EncodingError {
+    /// A line of an encoding configuration that is not of the form `shape.TOKEN = Shape` or
+    /// `outcome.TOKEN = Outcome`.
+    InvalidConfig {
+        line: usize,
+        content: String,
+    },
+    UnknownToken {
+        line: usize,
+        column: usize,
+        token: String,
+    },
+    MissingToken {
+        line: usize,
+        column: usize,
+    },
+}
+
+impl Display for EncodingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidConfig { line, content } => {
+                write!(f, "line {line}: invalid encoding entry {content:?}")
+            }
+            Self::UnknownToken {
+                line,
+                column,
+                token,
+            } => write!(f, "line {line}, column {column}: unknown token {token:?}"),
+            Self::MissingToken { line, column } => {
+                write!(f, "line {line}: missing token in column {column}")
+            }
+        }
+    }
+}
+
+/// Something a token of a strategy guide can stand for.
+pub trait Decode: Sized {
+    fn decode(encoding: &Encoding, token: &str) -> Option<Self>;
+}
+
+impl Decode for Shape {
+    fn decode(encoding: &Encoding, token: &str) -> Option<Self> {
+        encoding.shapes.get(token).copied()
+    }
+}
+
+impl Decode for Outcome {
+    fn decode(encoding: &Encoding, token: &str) -> Option<Self> {
+        encoding.outcomes.get(token).copied()
+    }
+}
+
+fn shape_from_name(name: &str) -> Option<Shape> {
+    match name.to_lowercase().as_str() {
+        "rock" => Some(Shape::Rock),
+        "paper" => Some(Shape::Paper),
+        "scissors" => Some(Shape::Scissors),
+        _ => None,
+    }
+}
+
+fn outcome_from_name(name: &str) -> Option<Outcome> {
+    match name.to_lowercase().as_str() {
+        "lose" | "loose" | "loss" => Some(Outcome::Loose),
+        "draw" => Some(Outcome::Draw),
+        "win" => Some(Outcome::Win),
+        _ => None,
+    }
+}
+
+/// Maps arbitrary tokens of a strategy guide to shapes and outcomes. A token may stand for a shape
+/// and an outcome at the same time; which one is used depends on how a column is decoded.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Encoding {
+    shapes: HashMap<String, Shape>,
+    outcomes: HashMap<String, Outcome>,
+}
+
+impl Default for Encoding {
+    /// The `A`/`B`/`C` and `X`/`Y`/`Z` encoding of the puzzle.
+    fn default() -> Self {
+        Self::new()
+            .with_shape("A", Shape::Rock)
+            .with_shape("B", Shape::Paper)
+            .with_shape("C", Shape::Scissors)
+            .with_shape("X", Shape::Rock)
+            .with_shape("Y", Shape::Paper)
+            .with_shape("Z", Shape::Scissors)
+            .with_outcome("X", Outcome::Loose)
+            .with_outcome("Y", Outcome::Draw)
+            .with_outcome("Z", Outcome::Win)
+    }
+}
+
+impl Encoding {
+    /// An encoding without any tokens.
+    pub fn new() -> Self {
+        Self {
+            shapes: HashMap::new(),
+            outcomes: HashMap::new(),
+        }
+    }
+
+    pub fn with_shape(mut self, token: &str, shape: Shape) -> Self {
+        self.shapes.insert(token.to_string(), shape);
+        self
+    }
+
+    pub fn with_outcome(mut self, token: &str, outcome: Outcome) -> Self {
+        self.outcomes.insert(token.to_string(), outcome);
+        self
+    }
+
+    /// Reads an encoding from `key = value` lines such as `shape.R = Rock` or `outcome.W = Win`.
+    /// Blank lines and lines starting with `#` are ignored.
+    pub fn from_config(config: &str) -> Result<Self, EncodingError> {
+        config
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .try_fold(Self::new(), |encoding, (line, content)| {
+                let invalid = || EncodingError::InvalidConfig {
+                    line,
+                    content: content.to_string(),
+                };
+
+                // Values never contain `=`, so splitting at the last one allows `=` in tokens.
+                let (key, value) = content.rsplit_once('=').ok_or_else(invalid)?;
+                let (kind, token) = key.trim().split_once('.').ok_or_else(invalid)?;
+                let value = value.trim();
+
+                match kind {
+                    "shape" => {
+                        shape_from_name(value).map(|shape| encoding.with_shape(token, shape))
+                    }
+                    "outcome" => outcome_from_name(value)
+                        .map(|outcome| encoding.with_outcome(token, outcome)),
+                    _ => None,
+                }
+                .ok_or_else(invalid)
+            })
+    }
+
+    pub fn decode<T: Decode>(&self, token: &str) -> Option<T> {
+        T::decode(self, token)
+    }
+
+    /// Parses a strategy guide with this encoding, reporting the position of the first token that
+    /// cannot be decoded. Lines and columns are counted from one.
+    pub fn parse<O: Decode, U: Decode>(&self, input: &str) -> Result<Vec<(O, U)>, EncodingError> {
+        input
+            .lines()
+            .enumerate()
+            .filter(|(_, round)| !round.trim().is_empty())
+            .map(|(index, round)| {
+                let line = index + 1;
+                let mut tokens = round.split_whitespace();
+                let mut next = |column: usize| {
+                    tokens
+                        .next()
+                        .ok_or(EncodingError::MissingToken { line, column })
+                };
+
+                let (opponent, ours) = (next(1)?, next(2)?);
+                let unknown = |column: usize, token: &str| EncodingError::UnknownToken {
+                    line,
+                    column,
+                    token: token.to_string(),
+                };
+
+                Ok((
+                    self.decode(opponent).ok_or_else(|| unknown(1, opponent))?,
+                    self.decode(ours).ok_or_else(|| unknown(2, ours))?,
+                ))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::day2::encoding::{Encoding, EncodingError};
+    use crate::day2::{part_1_with_encoding, part_2_with_encoding, Outcome, Shape};
+
+    const FILE_INPUT: &str = include_str!("input.txt");
+
+    #[test]
+    fn test_default_encoding() {
+        let encoding = Encoding::default();
+
+        assert_eq!(part_1_with_encoding(FILE_INPUT, &encoding), Ok(14297));
+        assert_eq!(part_2_with_encoding(FILE_INPUT, &encoding), Ok(10498));
+    }
+
+    #[test]
+    fn test_builder() {
+        let encoding = Encoding::new()
+            .with_shape("🪨", Shape::Rock)
+            .with_shape("📄", Shape::Paper)
+            .with_shape("✂️", Shape::Scissors);
+
+        assert_eq!(
+            encoding.parse::<Shape, Shape>("🪨 📄\r\n✂️ 🪨\n").unwrap(),
+            vec![(Shape::Rock, Shape::Paper), (Shape::Scissors, Shape::Rock)]
+        );
+    }
+
+    #[test]
+    fn test_from_config() {
+        let config = "\
+# full words
+shape.Rock = Rock
+shape.Paper = paper
+shape.Scissors = SCISSORS
+
+outcome.Lose = lose
+outcome.Draw = draw
+outcome.= = win
+";
+        let encoding = Encoding::from_config(config).unwrap();
+
+        assert_eq!(
+            encoding
+                .parse::<Shape, Shape>("Rock Paper\nScissors Rock")
+                .unwrap(),
+            vec![(Shape::Rock, Shape::Paper), (Shape::Scissors, Shape::Rock)]
+        );
+        assert_eq!(
+            part_1_with_encoding("Rock Paper\nPaper Rock\nScissors Scissors", &encoding),
+            Ok(15)
+        );
+        assert_eq!(
+            part_2_with_encoding("Rock Draw\nPaper Lose\nScissors =", &encoding),
+            Ok(12)
+        );
+        assert_eq!(
+            encoding
+                .parse::<Shape, Outcome>("Rock =\nPaper Lose")
+                .unwrap(),
+            vec![(Shape::Rock, Outcome::Win), (Shape::Paper, Outcome::Loose)]
+        );
+        assert_eq!(
+            Encoding::from_config("shape.R = Rock\nshape.P = Pebble"),
+            Err(EncodingError::InvalidConfig {
+                line: 2,
+                content: "shape.P = Pebble".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_unknown_tokens() {
+        let encoding = Encoding::default();
+
+        assert_eq!(
+            encoding.parse::<Shape, Shape>("A X\nB Q\n"),
+            Err(EncodingError::UnknownToken {
+                line: 2,
+                column: 2,
+                token: "Q".to_string()
+            })
+        );
+        assert_eq!(
+            encoding.parse::<Shape, Outcome>("A X\n\nD Y\n"),
+            Err(EncodingError::UnknownToken {
+                line: 3,
+                column: 1,
+                token: "D".to_string()
+            })
+        );
+        assert_eq!(
+            encoding.parse::<Shape, Shape>("A\n"),
+            Err(EncodingError::MissingToken { line: 1, column: 2 })
+        );
+    }
+}
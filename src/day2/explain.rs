@@ -0,0 +1,197 @@
+use std::fmt::Write;
+
+use crate::day2::{Outcome, Shape};
+
+/// How a single round of a strategy guide was scored.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct RoundReport {
+    pub opponent: Shape,
+    pub ours: Shape,
+    pub outcome: Outcome,
+    pub shape_points: u8,
+    pub outcome_points: u8,
+    pub running_total: u32,
+}
+
+/// Wins, draws and losses of every round in which we played a shape.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ShapeSummary {
+    pub shape: Shape,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+/// Scores the rounds one by one, given as `(opponent, ours)` pairs.
+pub fn round_reports<I>(rounds: I) -> impl Iterator<Item = RoundReport>
+where
+    I: IntoIterator<Item = (Shape, Shape)>,
+{
+    rounds
+        .into_iter()
+        .scan(0, |running_total, (opponent, ours)| {
+            let outcome = ours.round_outcome(opponent);
+            let shape_points = ours.get_score();
+            let outcome_points = u8::from(outcome);
+            *running_total += (shape_points + outcome_points) as u32;
+
+            Some(RoundReport {
+                opponent,
+                ours,
+                outcome,
+                shape_points,
+                outcome_points,
+                running_total: *running_total,
+            })
+        })
+}
+
+/// Turns the desired outcomes of part two into the shapes we have to play for them.
+pub fn resolve_outcomes<I>(rounds: I) -> impl Iterator<Item = (Shape, Shape)>
+where
+    I: IntoIterator<Item = (Shape, Outcome)>,
+{
+    rounds
+        .into_iter()
+        .map(|(opponent, outcome)| (opponent, opponent.get_shape_for_desired_outcome(outcome)))
+}
+
+pub fn summarize(reports: &[RoundReport]) -> Vec<ShapeSummary> {
+    [Shape::Rock, Shape::Paper, Shape::Scissors]
+        .into_iter()
+        .map(|shape| {
+            let count = |outcome: Outcome| {
+                reports
+                    .iter()
+                    .filter(|report| report.ours == shape && report.outcome == outcome)
+                    .count()
+            };
+
+            ShapeSummary {
+                shape,
+                wins: count(Outcome::Win),
+                draws: count(Outcome::Draw),
+                losses: count(Outcome::Loose),
+            }
+        })
+        .collect()
+}
+
+/// Renders one table row per round followed by the per-shape summary.
+pub fn render(reports: &[RoundReport]) -> String {
+    let mut table = format!(
+        "{:>6}  {:<8}  {:<8}  {:<7}  {:>5}  {:>7}  {:>7}\n",
+        "round", "opponent", "ours", "outcome", "shape", "outcome", "total"
+    );
+
+    for (round, report) in reports.iter().enumerate() {
+        writeln!(
+            table,
+            "{:>6}  {:<8}  {:<8}  {:<7}  {:>5}  {:>7}  {:>7}",
+            round + 1,
+            format!("{:?}", report.opponent),
+            format!("{:?}", report.ours),
+            format!("{:?}", report.outcome),
+            report.shape_points,
+            report.outcome_points,
+            report.running_total
+        )
+        .unwrap();
+    }
+
+    writeln!(
+        table,
+        "\n{:<8}  {:>6}  {:>6}  {:>6}",
+        "shape", "wins", "draws", "losses"
+    )
+    .unwrap();
+    for summary in summarize(reports) {
+        writeln!(
+            table,
+            "{:<8}  {:>6}  {:>6}  {:>6}",
+            format!("{:?}", summary.shape),
+            summary.wins,
+            summary.draws,
+            summary.losses
+        )
+        .unwrap();
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod test {
+    use crate::day2::explain::{render, resolve_outcomes, round_reports, summarize, RoundReport};
+    use crate::day2::{parse_input, part_1, part_2, Outcome, Shape};
+
+    const EXAMPLE_INPUT: &str = "A Y\nB X\nC Z\n";
+    const FILE_INPUT: &str = include_str!("input.txt");
+
+    #[test]
+    fn test_round_reports() {
+        let reports =
+            round_reports(parse_input::<Shape, Shape>(EXAMPLE_INPUT).unwrap()).collect::<Vec<_>>();
+
+        assert_eq!(
+            reports[0],
+            RoundReport {
+                opponent: Shape::Rock,
+                ours: Shape::Paper,
+                outcome: Outcome::Win,
+                shape_points: 2,
+                outcome_points: 6,
+                running_total: 8,
+            }
+        );
+        assert_eq!(
+            reports
+                .iter()
+                .map(|report| report.running_total)
+                .collect::<Vec<_>>(),
+            [8, 9, 15]
+        );
+    }
+
+    #[test]
+    fn test_totals_match_parts() {
+        let part_1_total = round_reports(parse_input::<Shape, Shape>(FILE_INPUT).unwrap())
+            .last()
+            .unwrap()
+            .running_total;
+        let part_2_total = round_reports(resolve_outcomes(parse_input(FILE_INPUT).unwrap()))
+            .last()
+            .unwrap()
+            .running_total;
+
+        assert_eq!(part_1_total as i32, part_1(FILE_INPUT).unwrap());
+        assert_eq!(part_2_total as i32, part_2(FILE_INPUT).unwrap());
+    }
+
+    #[test]
+    fn test_summarize() {
+        let reports = round_reports(resolve_outcomes(parse_input(EXAMPLE_INPUT).unwrap()))
+            .collect::<Vec<_>>();
+        let summary = summarize(&reports)
+            .into_iter()
+            .map(|summary| (summary.wins, summary.draws, summary.losses))
+            .collect::<Vec<_>>();
+
+        assert_eq!(summary, [(1, 1, 1), (0, 0, 0), (0, 0, 0)]);
+    }
+
+    #[test]
+    fn test_render() {
+        let reports =
+            round_reports(parse_input::<Shape, Shape>(EXAMPLE_INPUT).unwrap()).collect::<Vec<_>>();
+        let table = render(&reports);
+        let lines = table.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 9);
+        assert_eq!(
+            lines[1],
+            "     1  Rock      Paper     Win          2        6        8"
+        );
+        assert_eq!(lines[6], "Rock           0       0       1");
+    }
+}
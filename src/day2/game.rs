@@ -0,0 +1,254 @@
+use std::fmt::{Display, Formatter};
+
+use crate::day2::Outcome;
+
+/// Decides a round of a cyclic hand game with `shape_count` shapes by modular arithmetic: our
+/// shape beats the opponent's if the distance `(ours - theirs) mod shape_count` is odd. With an odd
+/// number of shapes every shape beats exactly half of the others, and the order
+/// `rock, paper, scissors, spock, lizard` yields the usual rules.
+pub fn cyclic_outcome(ours: usize, theirs: usize, shape_count: usize) -> Outcome {
+    let distance = (ours + shape_count - theirs) % shape_count;
+
+    if distance == 0 {
+        Outcome::Draw
+    } else if distance % 2 == 1 {
+        Outcome::Win
+    } else {
+        Outcome::Loose
+    }
+}
+
+/// Picks the shape that produces the desired outcome against the opponent's shape. The next shape
+/// in the cycle is at distance one and always wins, the previous one is at the (even) distance
+/// `shape_count - 1` and always loses.
+pub fn cyclic_shape_for_outcome(theirs: usize, outcome: Outcome, shape_count: usize) -> usize {
+    match outcome {
+        Outcome::Win => (theirs + 1) % shape_count,
+        Outcome::Draw => theirs,
+        Outcome::Loose => (theirs + shape_count - 1) % shape_count,
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum GameError {
+    EvenShapeCount(usize),
+    TooFewShapes(usize),
+    ScoreCountMismatch { shapes: usize, scores: usize },
+}
+
+impl Display for GameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EvenShapeCount(count) => {
+                write!(
+                    f,
+                    "a cyclic game needs an odd number of shapes, got {count}"
+                )
+            }
+            Self::TooFewShapes(count) => write!(f, "a game needs at least 3 shapes, got {count}"),
+            Self::ScoreCountMismatch { shapes, scores } => {
+                write!(f, "got {scores} shape scores for {shapes} shapes")
+            }
+        }
+    }
+}
+
+/// A cyclic hand game over any odd number of shapes, with configurable scores for the shapes and
+/// the outcomes. Shapes are identified by their index in the cycle.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Game {
+    names: Vec<String>,
+    shape_scores: Vec<u32>,
+    /// Points for losing, drawing and winning a round, in that order.
+    outcome_scores: [u32; 3],
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::rock_paper_scissors()
+    }
+}
+
+impl Game {
+    /// Creates a game where shapes score `1, 2, 3, ...` in cycle order and outcomes score like in
+    /// the puzzle.
+    pub fn new(names: &[&str]) -> Result<Self, GameError> {
+        if names.len() < 3 {
+            return Err(GameError::TooFewShapes(names.len()));
+        }
+        if names.len().is_multiple_of(2) {
+            return Err(GameError::EvenShapeCount(names.len()));
+        }
+
+        Ok(Self {
+            names: names.iter().map(|name| name.to_string()).collect(),
+            shape_scores: (1..=names.len() as u32).collect(),
+            outcome_scores: [0, 3, 6],
+        })
+    }
+
+    pub fn rock_paper_scissors() -> Self {
+        Self::new(&["Rock", "Paper", "Scissors"]).unwrap()
+    }
+
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        Self::new(&["Rock", "Paper", "Scissors", "Spock", "Lizard"]).unwrap()
+    }
+
+    pub fn with_shape_scores(mut self, scores: &[u32]) -> Result<Self, GameError> {
+        if scores.len() != self.names.len() {
+            return Err(GameError::ScoreCountMismatch {
+                shapes: self.names.len(),
+                scores: scores.len(),
+            });
+        }
+
+        self.shape_scores = scores.to_vec();
+        Ok(self)
+    }
+
+    pub fn with_outcome_scores(mut self, loose: u32, draw: u32, win: u32) -> Self {
+        self.outcome_scores = [loose, draw, win];
+        self
+    }
+
+    pub fn shape_count(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn name(&self, shape: usize) -> &str {
+        &self.names[shape]
+    }
+
+    pub fn shape(&self, name: &str) -> Option<usize> {
+        self.names
+            .iter()
+            .position(|candidate| candidate.eq_ignore_ascii_case(name))
+    }
+
+    pub fn shape_score(&self, shape: usize) -> u32 {
+        self.shape_scores[shape]
+    }
+
+    pub fn outcome_score(&self, outcome: Outcome) -> u32 {
+        match outcome {
+            Outcome::Loose => self.outcome_scores[0],
+            Outcome::Draw => self.outcome_scores[1],
+            Outcome::Win => self.outcome_scores[2],
+        }
+    }
+
+    pub fn outcome(&self, ours: usize, theirs: usize) -> Outcome {
+        cyclic_outcome(ours, theirs, self.shape_count())
+    }
+
+    /// The points we get for playing `ours` against `theirs`.
+    pub fn score(&self, ours: usize, theirs: usize) -> u32 {
+        self.shape_score(ours) + self.outcome_score(self.outcome(ours, theirs))
+    }
+
+    pub fn shape_for_outcome(&self, theirs: usize, outcome: Outcome) -> usize {
+        cyclic_shape_for_outcome(theirs, outcome, self.shape_count())
+    }
+
+    /// All shapes the given shape defeats.
+    pub fn beats(&self, shape: usize) -> Vec<usize> {
+        (0..self.shape_count())
+            .filter(|other| self.outcome(shape, *other) == Outcome::Win)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::day2::game::{Game, GameError};
+    use crate::day2::{parse_input, part_1, Outcome, Shape};
+
+    #[test]
+    fn test_default_game_matches_shapes() {
+        let game = Game::default();
+        let shapes = [Shape::Rock, Shape::Paper, Shape::Scissors];
+
+        for ours in shapes {
+            for theirs in shapes {
+                assert_eq!(
+                    game.score(ours.index(), theirs.index()),
+                    ours.play_against(theirs) as u32
+                );
+            }
+
+            for outcome in [Outcome::Loose, Outcome::Draw, Outcome::Win] {
+                assert_eq!(
+                    game.shape_for_outcome(ours.index(), outcome),
+                    ours.get_shape_for_desired_outcome(outcome).index()
+                );
+            }
+        }
+
+        let input = include_str!("input.txt");
+        let total = parse_input::<Shape, Shape>(input)
+            .unwrap()
+            .into_iter()
+            .map(|(theirs, ours)| game.score(ours.index(), theirs.index()))
+            .sum::<u32>();
+        assert_eq!(total as i32, part_1(input).unwrap());
+    }
+
+    #[test]
+    fn test_rock_paper_scissors_lizard_spock() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        let shape = |name| game.shape(name).unwrap();
+        let beats = |name| {
+            let mut names = game
+                .beats(shape(name))
+                .into_iter()
+                .map(|shape| game.name(shape))
+                .collect::<Vec<_>>();
+            names.sort_unstable();
+            names
+        };
+
+        assert_eq!(beats("Rock"), ["Lizard", "Scissors"]);
+        assert_eq!(beats("Paper"), ["Rock", "Spock"]);
+        assert_eq!(beats("Scissors"), ["Lizard", "Paper"]);
+        assert_eq!(beats("Spock"), ["Rock", "Scissors"]);
+        assert_eq!(beats("Lizard"), ["Paper", "Spock"]);
+
+        for theirs in 0..game.shape_count() {
+            for outcome in [Outcome::Loose, Outcome::Draw, Outcome::Win] {
+                let ours = game.shape_for_outcome(theirs, outcome);
+                assert_eq!(game.outcome(ours, theirs), outcome);
+            }
+        }
+    }
+
+    #[test]
+    fn test_configurable_scores() {
+        let game = Game::new(&["A", "B", "C", "D", "E", "F", "G"])
+            .unwrap()
+            .with_shape_scores(&[10, 20, 30, 40, 50, 60, 70])
+            .unwrap()
+            .with_outcome_scores(1, 2, 5);
+
+        assert_eq!(game.score(1, 0), 25);
+        assert_eq!(game.score(2, 0), 31);
+        assert_eq!(game.score(6, 6), 72);
+        assert_eq!(game.beats(0), [2, 4, 6]);
+    }
+
+    #[test]
+    fn test_invalid_games() {
+        assert_eq!(Game::new(&["A", "B"]), Err(GameError::TooFewShapes(2)));
+        assert_eq!(
+            Game::new(&["A", "B", "C", "D"]),
+            Err(GameError::EvenShapeCount(4))
+        );
+        assert_eq!(
+            Game::default().with_shape_scores(&[1, 2]),
+            Err(GameError::ScoreCountMismatch {
+                shapes: 3,
+                scores: 2
+            })
+        );
+    }
+}
@@ -1,21 +1,20 @@
+use encoding::{Decode, Encoding, EncodingError};
+use game::{cyclic_outcome, cyclic_shape_for_outcome};
+
+pub mod decrypt;
+pub mod encoding;
+pub mod explain;
+pub mod game;
+pub mod theory;
+pub mod tournament;
+
 #[derive(Copy, Clone, PartialEq, Debug)]
-enum Outcome {
+pub enum Outcome {
     Loose,
     Draw,
     Win,
 }
 
-impl From<&str> for Outcome {
-    fn from(string: &str) -> Self {
-        match string {
-            "X" => Outcome::Loose,
-            "Y" => Outcome::Draw,
-            "Z" => Outcome::Win,
-            _ => unreachable!(),
-        }
-    }
-}
-
 impl From<Outcome> for u8 {
     fn from(outcome: Outcome) -> Self {
         match outcome {
@@ -27,23 +26,12 @@ impl From<Outcome> for u8 {
 }
 
 #[derive(Copy, Clone, PartialEq, Debug)]
-enum Shape {
+pub enum Shape {
     Rock,
     Paper,
     Scissors,
 }
 
-impl From<&str> for Shape {
-    fn from(string: &str) -> Self {
-        match string {
-            "A" | "X" => Self::Rock,
-            "B" | "Y" => Self::Paper,
-            "C" | "Z" => Self::Scissors,
-            _ => unreachable!(),
-        }
-    }
-}
-
 impl Shape {
     fn get_score(&self) -> u8 {
         match self {
@@ -53,91 +41,85 @@ impl Shape {
         }
     }
 
+    /// Position of the shape in the cycle of the default rock, paper, scissors game.
+    fn index(&self) -> usize {
+        match self {
+            Self::Rock => 0,
+            Self::Paper => 1,
+            Self::Scissors => 2,
+        }
+    }
+
+    fn from_index(index: usize) -> Self {
+        match index {
+            0 => Self::Rock,
+            1 => Self::Paper,
+            2 => Self::Scissors,
+            _ => unreachable!(),
+        }
+    }
+
     /// Simulates a "round" of rock, paper, scissors and returns the correct amount of points as
     /// defined in the problem.
     fn play_against(&self, opponent_shape: Self) -> u8 {
-        let round_outcome = match self {
-            Self::Rock => match opponent_shape {
-                Self::Rock => Outcome::Draw,
-                Self::Paper => Outcome::Loose,
-                Self::Scissors => Outcome::Win,
-            },
-            Self::Paper => match opponent_shape {
-                Self::Rock => Outcome::Win,
-                Self::Paper => Outcome::Draw,
-                Self::Scissors => Outcome::Loose,
-            },
-            Self::Scissors => match opponent_shape {
-                Self::Rock => Outcome::Loose,
-                Self::Paper => Outcome::Win,
-                Self::Scissors => Outcome::Draw,
-            },
-        };
-
-        self.get_score() + u8::from(round_outcome)
+        self.get_score() + u8::from(self.round_outcome(opponent_shape))
+    }
+
+    fn round_outcome(&self, opponent_shape: Self) -> Outcome {
+        cyclic_outcome(self.index(), opponent_shape.index(), 3)
     }
 
     /// Using the opponents shape, determines what shape we have to play to trigger the desired
     /// outcome.
     fn get_shape_for_desired_outcome(&self, outcome: Outcome) -> Self {
-        match outcome {
-            Outcome::Win => match self {
-                Self::Rock => Self::Paper,
-                Self::Paper => Self::Scissors,
-                Self::Scissors => Self::Rock,
-            },
-            Outcome::Draw => match self {
-                Self::Rock => Self::Rock,
-                Self::Paper => Self::Paper,
-                Self::Scissors => Self::Scissors,
-            },
-            Outcome::Loose => match self {
-                Self::Rock => Self::Scissors,
-                Self::Paper => Self::Rock,
-                Self::Scissors => Self::Paper,
-            },
-        }
+        Self::from_index(cyclic_shape_for_outcome(self.index(), outcome, 3))
     }
 }
 
-pub fn part_1(input: &str) -> i32 {
-    parse_input::<Shape, Shape>(input)
+pub fn part_1(input: &str) -> Result<i32, EncodingError> {
+    parse_input(input).map(score_shapes)
+}
+
+pub fn part_2(input: &str) -> Result<i32, EncodingError> {
+    parse_input(input).map(score_outcomes)
+}
+
+/// Like [`part_1`], but reads the guide with a custom symbol encoding.
+pub fn part_1_with_encoding(input: &str, encoding: &Encoding) -> Result<i32, EncodingError> {
+    encoding.parse(input).map(score_shapes)
+}
+
+/// Like [`part_2`], but reads the guide with a custom symbol encoding.
+pub fn part_2_with_encoding(input: &str, encoding: &Encoding) -> Result<i32, EncodingError> {
+    encoding.parse(input).map(score_outcomes)
+}
+
+fn score_shapes(rounds: Vec<(Shape, Shape)>) -> i32 {
+    rounds
         .into_iter()
         .fold(0, |acc, (opponent_shape, our_shape)| {
             acc + our_shape.play_against(opponent_shape) as i32
         })
 }
 
-pub fn part_2(input: &str) -> i32 {
-    parse_input::<Shape, Outcome>(input).into_iter().fold(
-        0,
-        |acc, (opponent_shape, desired_outcome)| {
+fn score_outcomes(rounds: Vec<(Shape, Outcome)>) -> i32 {
+    rounds
+        .into_iter()
+        .fold(0, |acc, (opponent_shape, desired_outcome)| {
             acc + opponent_shape
                 .get_shape_for_desired_outcome(desired_outcome)
                 .play_against(opponent_shape) as i32
-        },
-    )
+        })
 }
 
-fn parse_input<O, U>(input: &str) -> Vec<(O, U)>
-where
-    O: for<'a> From<&'a str>,
-    U: for<'a> From<&'a str>,
-{
-    input
-        .trim()
-        .split('\n')
-        .map(|round| {
-            let mut split = round.split_whitespace();
-            let mut next = || split.next().unwrap();
-
-            (next().into(), next().into())
-        })
-        .collect()
+/// Reads a strategy guide with the `A`/`B`/`C` and `X`/`Y`/`Z` encoding of the puzzle.
+fn parse_input<O: Decode, U: Decode>(input: &str) -> Result<Vec<(O, U)>, EncodingError> {
+    Encoding::default().parse(input)
 }
 
 #[cfg(test)]
 mod test {
+    use crate::day2::encoding::EncodingError;
     use crate::day2::{parse_input, part_1, part_2, Outcome, Shape};
 
     const EXAMPLE_INPUT: &str = "A Y\nB X\nC Z\n";
@@ -145,20 +127,44 @@ mod test {
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(EXAMPLE_INPUT), 15);
-        assert_eq!(part_1(FILE_INPUT), 14297);
+        assert_eq!(part_1(EXAMPLE_INPUT), Ok(15));
+        assert_eq!(part_1(FILE_INPUT), Ok(14297));
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(EXAMPLE_INPUT), 12);
-        assert_eq!(part_2(FILE_INPUT), 10498);
+        assert_eq!(part_2(EXAMPLE_INPUT), Ok(12));
+        assert_eq!(part_2(FILE_INPUT), Ok(10498));
+    }
+
+    #[test]
+    fn test_unknown_tokens() {
+        assert_eq!(
+            part_1("A Y\nA Q\n"),
+            Err(EncodingError::UnknownToken {
+                line: 2,
+                column: 2,
+                token: "Q".to_string()
+            })
+        );
+        assert_eq!(
+            part_2("D X"),
+            Err(EncodingError::UnknownToken {
+                line: 1,
+                column: 1,
+                token: "D".to_string()
+            })
+        );
+        assert_eq!(
+            part_2("A"),
+            Err(EncodingError::MissingToken { line: 1, column: 2 })
+        );
     }
 
     #[test]
     fn test_prepare_input_for_part_1() {
-        let result = parse_input(EXAMPLE_INPUT);
-        let expected = vec![
+        let result = parse_input(EXAMPLE_INPUT).unwrap();
+        let expected = [
             (Shape::Rock, Shape::Paper),
             (Shape::Paper, Shape::Rock),
             (Shape::Scissors, Shape::Scissors),
@@ -172,8 +178,8 @@ mod test {
 
     #[test]
     fn test_prepare_input_for_part_2() {
-        let result = parse_input(EXAMPLE_INPUT);
-        let expected = vec![
+        let result = parse_input(EXAMPLE_INPUT).unwrap();
+        let expected = [
             (Shape::Rock, Outcome::Draw),
             (Shape::Paper, Outcome::Loose),
             (Shape::Scissors, Outcome::Win),
@@ -0,0 +1,319 @@
+use std::fmt::{Display, Formatter};
+
+use crate::day2::encoding::EncodingError;
+use crate::day2::game::Game;
+use crate::day2::{parse_input, Shape};
+
+/// Probabilities of playing each shape of a game, indexed by shape.
+pub type MixedStrategy = Vec<f64>;
+
+/// `matrix[ours][theirs]` is the score we get for playing `ours` against `theirs`.
+pub fn payoff_matrix(game: &Game) -> Vec<Vec<u32>> {
+    (0..game.shape_count())
+        .map(|ours| {
+            (0..game.shape_count())
+                .map(|theirs| game.score(ours, theirs))
+                .collect()
+        })
+        .collect()
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum DistributionError {
+    Encoding(EncodingError),
+    /// A guide without rounds says nothing about the opponent.
+    NoRounds,
+}
+
+impl From<EncodingError> for DistributionError {
+    fn from(error: EncodingError) -> Self {
+        Self::Encoding(error)
+    }
+}
+
+impl Display for DistributionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Encoding(error) => write!(f, "{error}"),
+            Self::NoRounds => write!(f, "the strategy guide has no rounds"),
+        }
+    }
+}
+
+/// Estimates how often the opponent plays each shape from the first column of a strategy guide.
+pub fn opponent_distribution(input: &str) -> Result<MixedStrategy, DistributionError> {
+    let rounds = parse_input::<Shape, Shape>(input)?;
+    if rounds.is_empty() {
+        return Err(DistributionError::NoRounds);
+    }
+
+    let mut counts = vec![0.0; 3];
+    for (opponent, _) in &rounds {
+        counts[opponent.index()] += 1.0;
+    }
+
+    Ok(counts
+        .into_iter()
+        .map(|count| count / rounds.len() as f64)
+        .collect())
+}
+
+pub fn expected_score(game: &Game, ours: &[f64], theirs: &[f64]) -> f64 {
+    let matrix = payoff_matrix(game);
+
+    ours.iter()
+        .enumerate()
+        .flat_map(|(i, p)| {
+            let row = &matrix[i];
+            theirs
+                .iter()
+                .enumerate()
+                .map(move |(j, q)| p * q * row[j] as f64)
+        })
+        .sum()
+}
+
+/// The pure shape with the highest expected score against the given opponent, together with that
+/// score.
+pub fn best_response(game: &Game, theirs: &[f64]) -> (usize, f64) {
+    (0..game.shape_count())
+        .map(|shape| {
+            let mut pure = vec![0.0; game.shape_count()];
+            pure[shape] = 1.0;
+            (shape, expected_score(game, &pure, theirs))
+        })
+        .fold((0, f64::MIN), |best, candidate| {
+            if candidate.1 > best.1 {
+                candidate
+            } else {
+                best
+            }
+        })
+}
+
+/// Solves `matrix * x = rhs` by Gaussian elimination with partial pivoting.
+fn solve_linear_system(mut matrix: Vec<Vec<f64>>, mut rhs: Vec<f64>) -> Option<Vec<f64>> {
+    let n = rhs.len();
+
+    for column in 0..n {
+        let pivot = (column..n).max_by(|a, b| {
+            matrix[*a][column]
+                .abs()
+                .total_cmp(&matrix[*b][column].abs())
+        })?;
+        if matrix[pivot][column].abs() < 1e-12 {
+            return None;
+        }
+        matrix.swap(column, pivot);
+        rhs.swap(column, pivot);
+
+        for row in column + 1..n {
+            let factor = matrix[row][column] / matrix[column][column];
+            let (upper, lower) = matrix.split_at_mut(row);
+            for (target, source) in lower[0][column..].iter_mut().zip(&upper[column][column..]) {
+                *target -= factor * source;
+            }
+            rhs[row] -= factor * rhs[column];
+        }
+    }
+
+    let mut solution = vec![0.0; n];
+    for row in (0..n).rev() {
+        let known = (row + 1..n)
+            .map(|k| matrix[row][k] * solution[k])
+            .sum::<f64>();
+        solution[row] = (rhs[row] - known) / matrix[row][row];
+    }
+
+    Some(solution)
+}
+
+/// The fully mixed Nash equilibrium of the game, in which both players score the same because
+/// the game is symmetric. The shape scores make the game lopsided, so this is not the uniform
+/// strategy: the equilibrium strategy `q` is the one against which all of our shapes score the
+/// same, i.e. `payoff * q = v` for some value `v` with `sum(q) = 1`. Facing `q` leaves us
+/// indifferent between our shapes, so by symmetry both players playing `q` is an equilibrium.
+///
+/// Returns the strategy and its value, or `None` if no fully mixed equilibrium exists.
+pub fn nash_equilibrium(game: &Game) -> Option<(MixedStrategy, f64)> {
+    let n = game.shape_count();
+    let payoff = payoff_matrix(game);
+
+    // Unknowns are q_0..q_n-1 followed by v.
+    let mut matrix = payoff
+        .iter()
+        .map(|row| {
+            let mut equation = row.iter().map(|score| *score as f64).collect::<Vec<_>>();
+            equation.push(-1.0);
+            equation
+        })
+        .collect::<Vec<_>>();
+    let mut normalisation = vec![1.0; n];
+    normalisation.push(0.0);
+    matrix.push(normalisation);
+
+    let mut rhs = vec![0.0; n];
+    rhs.push(1.0);
+
+    let mut solution = solve_linear_system(matrix, rhs)?;
+    let value = solution.pop()?;
+
+    if solution.iter().any(|p| *p < -1e-9) {
+        return None;
+    }
+
+    Some((solution, value))
+}
+
+/// SplitMix64, a tiny seedable generator so simulations are reproducible without dependencies.
+pub struct SplitMix64(u64);
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A uniformly distributed float in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Draws an index according to the given probabilities.
+    pub fn sample(&mut self, strategy: &[f64]) -> usize {
+        let mut remaining = self.next_f64();
+        for (index, p) in strategy.iter().enumerate() {
+            if remaining < *p {
+                return index;
+            }
+            remaining -= p;
+        }
+
+        strategy.len() - 1
+    }
+}
+
+/// Plays `rounds` random rounds between two mixed strategies and returns our average score.
+pub fn simulate(game: &Game, ours: &[f64], theirs: &[f64], rounds: usize, seed: u64) -> f64 {
+    let mut rng = SplitMix64::new(seed);
+    let total = (0..rounds)
+        .map(|_| game.score(rng.sample(ours), rng.sample(theirs)) as u64)
+        .sum::<u64>();
+
+    total as f64 / rounds as f64
+}
+
+#[cfg(test)]
+mod test {
+    use crate::day2::encoding::EncodingError;
+    use crate::day2::game::Game;
+    use crate::day2::theory::{
+        best_response, expected_score, nash_equilibrium, opponent_distribution, payoff_matrix,
+        simulate, DistributionError,
+    };
+
+    const EXAMPLE_INPUT: &str = "A Y\nB X\nC Z\n";
+    const FILE_INPUT: &str = include_str!("input.txt");
+
+    fn assert_close(a: f64, b: f64, tolerance: f64) {
+        assert!((a - b).abs() < tolerance, "{a} is not close to {b}");
+    }
+
+    #[test]
+    fn test_payoff_matrix() {
+        assert_eq!(
+            payoff_matrix(&Game::default()),
+            vec![vec![4, 1, 7], vec![8, 5, 2], vec![3, 9, 6]]
+        );
+    }
+
+    #[test]
+    fn test_opponent_distribution() {
+        assert_eq!(
+            opponent_distribution("A Y\nA X\nC Z\nB Z\n"),
+            Ok(vec![0.5, 0.25, 0.25])
+        );
+        for input in ["", "\n  \n"] {
+            assert_eq!(
+                opponent_distribution(input),
+                Err(DistributionError::NoRounds)
+            );
+        }
+        assert_eq!(
+            opponent_distribution("A Q"),
+            Err(DistributionError::Encoding(EncodingError::UnknownToken {
+                line: 1,
+                column: 2,
+                token: "Q".to_string()
+            }))
+        );
+    }
+
+    #[test]
+    fn test_best_response() {
+        let game = Game::default();
+        let opponent = opponent_distribution(EXAMPLE_INPUT).unwrap();
+
+        assert_close(
+            expected_score(&game, &[0.0, 1.0, 0.0], &opponent),
+            5.0,
+            1e-9,
+        );
+        assert_eq!(best_response(&game, &opponent).0, 2);
+        assert_eq!(best_response(&game, &[1.0, 0.0, 0.0]), (1, 8.0));
+    }
+
+    #[test]
+    fn test_nash_equilibrium() {
+        let game = Game::default();
+        let (strategy, value) = nash_equilibrium(&game).unwrap();
+
+        for (p, expected) in strategy.iter().zip([4.0 / 9.0, 1.0 / 9.0, 4.0 / 9.0]) {
+            assert_close(*p, expected, 1e-9);
+        }
+        assert_close(value, 5.0, 1e-9);
+
+        // Against the equilibrium every shape scores the same.
+        for shape in 0..3 {
+            let mut pure = vec![0.0; 3];
+            pure[shape] = 1.0;
+            assert_close(expected_score(&game, &pure, &strategy), value, 1e-9);
+        }
+
+        // With equal shape scores the game is balanced and the equilibrium is uniform.
+        let balanced = Game::rock_paper_scissors_lizard_spock()
+            .with_shape_scores(&[1; 5])
+            .unwrap();
+        let (strategy, value) = nash_equilibrium(&balanced).unwrap();
+        for p in strategy {
+            assert_close(p, 0.2, 1e-9);
+        }
+        assert_close(value, 4.0, 1e-9);
+    }
+
+    #[test]
+    fn test_simulation_matches_expectation() {
+        let game = Game::default();
+        let opponent = opponent_distribution(FILE_INPUT).unwrap();
+        let (equilibrium, _) = nash_equilibrium(&game).unwrap();
+
+        for ours in [vec![0.2, 0.3, 0.5], equilibrium] {
+            let expected = expected_score(&game, &ours, &opponent);
+            let simulated = simulate(&game, &ours, &opponent, 200_000, 2022);
+
+            assert_close(simulated, expected, 0.05);
+        }
+
+        assert_eq!(
+            simulate(&game, &[0.5, 0.5, 0.0], &opponent, 1000, 7),
+            simulate(&game, &[0.5, 0.5, 0.0], &opponent, 1000, 7)
+        );
+    }
+}
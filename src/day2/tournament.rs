@@ -0,0 +1,376 @@
+use std::cmp::{Ordering, Reverse};
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+use crate::day2::encoding::{Encoding, EncodingError};
+use crate::day2::{Outcome, Shape};
+
+/// A strategy guide entered into a tournament. Its second column is read as the shapes to play.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Guide {
+    pub name: String,
+    pub shapes: Vec<Shape>,
+}
+
+impl Guide {
+    pub fn parse(name: &str, input: &str) -> Result<Self, EncodingError> {
+        Ok(Self {
+            name: name.to_string(),
+            shapes: Encoding::default()
+                .parse::<Shape, Shape>(input)?
+                .into_iter()
+                .map(|(_, ours)| ours)
+                .collect(),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    /// A file that is not a valid strategy guide.
+    Guide { name: String, error: EncodingError },
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io { path, error } => write!(f, "{}: {error}", path.display()),
+            Self::Guide { name, error } => write!(f, "{name}: {error}"),
+        }
+    }
+}
+
+/// Loads every guide from the given paths. Directories contribute all files they directly
+/// contain, in name order, and every guide is named after its file.
+pub fn load_guides<P: AsRef<Path>>(paths: &[P]) -> Result<Vec<Guide>, LoadError> {
+    let io_error = |path: &Path| {
+        let path = path.to_path_buf();
+        move |error| LoadError::Io { path, error }
+    };
+
+    let mut files = Vec::new();
+    for path in paths {
+        let path = path.as_ref();
+        if path.is_dir() {
+            let mut entries = std::fs::read_dir(path)
+                .and_then(|entries| {
+                    entries
+                        .map(|entry| entry.map(|entry| entry.path()))
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .map_err(io_error(path))?;
+            entries.retain(|entry| entry.is_file());
+            entries.sort();
+            files.extend(entries);
+        } else {
+            files.push(path.to_path_buf());
+        }
+    }
+
+    files
+        .iter()
+        .map(|file| {
+            let name = file
+                .file_name()
+                .unwrap_or(file.as_os_str())
+                .to_string_lossy();
+            let input = std::fs::read_to_string(file).map_err(io_error(file))?;
+
+            Guide::parse(&name, &input).map_err(|error| LoadError::Guide {
+                name: name.to_string(),
+                error,
+            })
+        })
+        .collect()
+}
+
+/// The result of two guides playing each other round by round. Matches last as many rounds as
+/// the shorter guide has.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct MatchResult {
+    pub home: usize,
+    pub away: usize,
+    pub rounds: usize,
+    pub home_score: u32,
+    pub away_score: u32,
+    pub home_round_wins: usize,
+    pub round_draws: usize,
+    pub away_round_wins: usize,
+}
+
+impl MatchResult {
+    fn play(home: usize, away: usize, guides: &[Guide]) -> Self {
+        let mut result = Self {
+            home,
+            away,
+            rounds: 0,
+            home_score: 0,
+            away_score: 0,
+            home_round_wins: 0,
+            round_draws: 0,
+            away_round_wins: 0,
+        };
+
+        for (home_shape, away_shape) in guides[home].shapes.iter().zip(&guides[away].shapes) {
+            result.rounds += 1;
+            result.home_score += home_shape.play_against(*away_shape) as u32;
+            result.away_score += away_shape.play_against(*home_shape) as u32;
+
+            match home_shape.round_outcome(*away_shape) {
+                Outcome::Win => result.home_round_wins += 1,
+                Outcome::Draw => result.round_draws += 1,
+                Outcome::Loose => result.away_round_wins += 1,
+            }
+        }
+
+        result
+    }
+
+    /// The match is decided by the total score of both sides.
+    pub fn home_outcome(&self) -> Outcome {
+        match self.home_score.cmp(&self.away_score) {
+            Ordering::Greater => Outcome::Win,
+            Ordering::Equal => Outcome::Draw,
+            Ordering::Less => Outcome::Loose,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Standing {
+    pub name: String,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    pub score: u32,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Tournament {
+    pub guides: Vec<Guide>,
+    pub matches: Vec<MatchResult>,
+}
+
+/// Lets every guide play every other guide exactly once.
+pub fn round_robin(guides: Vec<Guide>) -> Tournament {
+    let matches = (0..guides.len())
+        .flat_map(|home| (home + 1..guides.len()).map(move |away| (home, away)))
+        .map(|(home, away)| MatchResult::play(home, away, &guides))
+        .collect();
+
+    Tournament { guides, matches }
+}
+
+impl Tournament {
+    /// Standings ordered by wins, then draws, then total score.
+    pub fn leaderboard(&self) -> Vec<Standing> {
+        let mut standings = self
+            .guides
+            .iter()
+            .map(|guide| Standing {
+                name: guide.name.clone(),
+                wins: 0,
+                draws: 0,
+                losses: 0,
+                score: 0,
+            })
+            .collect::<Vec<_>>();
+
+        for result in &self.matches {
+            standings[result.home].score += result.home_score;
+            standings[result.away].score += result.away_score;
+
+            match result.home_outcome() {
+                Outcome::Win => {
+                    standings[result.home].wins += 1;
+                    standings[result.away].losses += 1;
+                }
+                Outcome::Draw => {
+                    standings[result.home].draws += 1;
+                    standings[result.away].draws += 1;
+                }
+                Outcome::Loose => {
+                    standings[result.home].losses += 1;
+                    standings[result.away].wins += 1;
+                }
+            }
+        }
+
+        standings.sort_by_key(|standing| Reverse((standing.wins, standing.draws, standing.score)));
+        standings
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "home,away,rounds,home_score,away_score,home_round_wins,round_draws,away_round_wins\n",
+        );
+
+        for result in &self.matches {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{}\n",
+                csv_field(&self.guides[result.home].name),
+                csv_field(&self.guides[result.away].name),
+                result.rounds,
+                result.home_score,
+                result.away_score,
+                result.home_round_wins,
+                result.round_draws,
+                result.away_round_wins,
+            ));
+        }
+
+        csv
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+impl Display for Tournament {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let leaderboard = self.leaderboard();
+        let name_width = leaderboard
+            .iter()
+            .map(|standing| standing.name.len())
+            .max()
+            .unwrap_or(0)
+            .max(5);
+
+        writeln!(
+            f,
+            "{:>4}  {:<name_width$}  {:>4}  {:>4}  {:>4}  {:>8}",
+            "#", "guide", "W", "D", "L", "score"
+        )?;
+        for (rank, standing) in leaderboard.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>4}  {:<name_width$}  {:>4}  {:>4}  {:>4}  {:>8}",
+                rank + 1,
+                standing.name,
+                standing.wins,
+                standing.draws,
+                standing.losses,
+                standing.score
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::day2::encoding::EncodingError;
+    use crate::day2::tournament::{load_guides, round_robin, Guide, LoadError, MatchResult};
+
+    fn guides() -> Vec<Guide> {
+        vec![
+            Guide::parse("rock", "A X\nB X\nC X\n").unwrap(),
+            Guide::parse("paper", "A Y\nB Y\nC Y\nA Y\n").unwrap(),
+            Guide::parse("cycle", "A X\nA Y\nA Z\n").unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_round_robin() {
+        let tournament = round_robin(guides());
+
+        assert_eq!(tournament.matches.len(), 3);
+        assert_eq!(
+            tournament.matches[0],
+            MatchResult {
+                home: 0,
+                away: 1,
+                rounds: 3,
+                home_score: 3,
+                away_score: 24,
+                home_round_wins: 0,
+                round_draws: 0,
+                away_round_wins: 3,
+            }
+        );
+
+        let leaderboard = tournament.leaderboard();
+        let names = leaderboard
+            .iter()
+            .map(|standing| standing.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["paper", "cycle", "rock"]);
+        assert_eq!(
+            (
+                leaderboard[0].wins,
+                leaderboard[0].draws,
+                leaderboard[0].losses
+            ),
+            (1, 1, 0)
+        );
+        assert_eq!(
+            leaderboard
+                .iter()
+                .map(|s| s.wins + s.draws + s.losses)
+                .sum::<usize>(),
+            6
+        );
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let csv = round_robin(guides()).to_csv();
+        let lines = csv.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[1], "rock,paper,3,3,24,0,0,3");
+    }
+
+    #[test]
+    fn test_load_guides() {
+        let directory = std::env::temp_dir().join(format!("aoc-2022-day2-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(directory.join("b.txt"), "A Y\n").unwrap();
+        std::fs::write(directory.join("a.txt"), "A X\n").unwrap();
+
+        let guides = load_guides(&[&directory]).unwrap();
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(guides.len(), 2);
+        assert_eq!(guides[0].name, "a.txt");
+        assert_eq!(guides[1].name, "b.txt");
+    }
+
+    #[test]
+    fn test_load_malformed_guide() {
+        let directory =
+            std::env::temp_dir().join(format!("aoc-2022-day2-malformed-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(directory.join("a.txt"), "A X\n").unwrap();
+        std::fs::write(directory.join("README"), "A Q\n").unwrap();
+
+        let result = load_guides(&[&directory]);
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        let Err(error @ LoadError::Guide { .. }) = result else {
+            panic!("expected a guide error, got {result:?}");
+        };
+        assert_eq!(
+            error.to_string(),
+            "README: line 1, column 2: unknown token \"Q\""
+        );
+        assert!(matches!(
+            Guide::parse("empty", ""),
+            Ok(Guide { shapes, .. }) if shapes.is_empty()
+        ));
+        assert_eq!(
+            Guide::parse("short", "A X\nB\n"),
+            Err(EncodingError::MissingToken { line: 2, column: 2 })
+        );
+    }
+}
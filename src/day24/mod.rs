@@ -0,0 +1,230 @@
+use std::collections::{HashSet, VecDeque};
+
+type Position = (usize, usize);
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl TryFrom<char> for Direction {
+    type Error = ();
+
+    fn try_from(char: char) -> Result<Self, Self::Error> {
+        match char {
+            '^' => Ok(Self::Up),
+            'v' => Ok(Self::Down),
+            '<' => Ok(Self::Left),
+            '>' => Ok(Self::Right),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct Blizzard {
+    pub position: Position,
+    pub direction: Direction,
+}
+
+struct Valley {
+    pub width: usize,
+    pub height: usize,
+    pub start: Position,
+    pub goal: Position,
+    pub period: usize,
+    /// One flattened `width * height` occupancy grid of the valley interior per minute of the
+    /// blizzard cycle.
+    occupied: Vec<Vec<bool>>,
+}
+
+impl Valley {
+    fn new(
+        width: usize,
+        height: usize,
+        start: Position,
+        goal: Position,
+        blizzards: &[Blizzard],
+    ) -> Self {
+        let period = lcm(width, height);
+        let occupied = (0..period)
+            .map(|time| {
+                let mut grid = vec![false; width * height];
+                for blizzard in blizzards {
+                    let (x, y) = blizzard_position_at(blizzard, width, height, time);
+                    grid[(y - 1) * width + (x - 1)] = true;
+                }
+
+                grid
+            })
+            .collect();
+
+        Self {
+            width,
+            height,
+            start,
+            goal,
+            period,
+            occupied,
+        }
+    }
+
+    /// Whether the expedition may stand on the given position at the given minute.
+    fn is_open(&self, (x, y): Position, time: usize) -> bool {
+        if (x, y) == self.start || (x, y) == self.goal {
+            return true;
+        }
+
+        if x == 0 || x > self.width || y == 0 || y > self.height {
+            return false;
+        }
+
+        !self.occupied[time % self.period][(y - 1) * self.width + (x - 1)]
+    }
+
+    fn neighbours(&self, (x, y): Position) -> Vec<Position> {
+        let mut neighbours = vec![(x, y), (x + 1, y), (x, y + 1)];
+        if x > 0 {
+            neighbours.push((x - 1, y));
+        }
+        if y > 0 {
+            neighbours.push((x, y - 1));
+        }
+
+        neighbours
+    }
+
+    /// Breadth-first search over `(position, time mod period)`. Since the blizzards repeat every
+    /// `period` minutes, two visits to the same position at the same phase of the cycle are
+    /// equivalent and only the first one has to be explored. Returns the minute of arrival.
+    fn fastest_crossing(&self, from: Position, to: Position, start_time: usize) -> Option<usize> {
+        let mut visited = HashSet::from([(from, start_time % self.period)]);
+        let mut queue = VecDeque::from([(from, start_time)]);
+
+        while let Some((position, time)) = queue.pop_front() {
+            if position == to {
+                return Some(time);
+            }
+
+            for next in self.neighbours(position) {
+                let next_time = time + 1;
+                if self.is_open(next, next_time) && visited.insert((next, next_time % self.period))
+                {
+                    queue.push_back((next, next_time));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn lcm(a: usize, b: usize) -> usize {
+    a / gcd(a, b) * b
+}
+
+/// Blizzards wrap around inside the walls, so their position at any minute can be computed
+/// directly instead of being simulated step by step.
+fn blizzard_position_at(blizzard: &Blizzard, width: usize, height: usize, time: usize) -> Position {
+    let (x, y) = blizzard.position;
+    let wrap = |coordinate: usize, size: usize, offset: usize| (coordinate - 1 + offset) % size + 1;
+
+    match blizzard.direction {
+        Direction::Right => (wrap(x, width, time % width), y),
+        Direction::Left => (wrap(x, width, width - time % width), y),
+        Direction::Down => (x, wrap(y, height, time % height)),
+        Direction::Up => (x, wrap(y, height, height - time % height)),
+    }
+}
+
+fn parse_input(input: &str) -> Valley {
+    let rows = input.trim().lines().collect::<Vec<_>>();
+    let find_opening = |row: &str| row.chars().position(|char| char == '.').unwrap();
+
+    let width = rows[0].len() - 2;
+    let height = rows.len() - 2;
+    let start = (find_opening(rows[0]), 0);
+    let goal = (find_opening(rows[rows.len() - 1]), rows.len() - 1);
+
+    let blizzards = rows
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.chars().enumerate().filter_map(move |(x, char)| {
+                Direction::try_from(char).ok().map(|direction| Blizzard {
+                    position: (x, y),
+                    direction,
+                })
+            })
+        })
+        .collect::<Vec<_>>();
+
+    Valley::new(width, height, start, goal, &blizzards)
+}
+
+pub fn part_1(input: &str) -> usize {
+    let valley = parse_input(input);
+
+    valley
+        .fastest_crossing(valley.start, valley.goal, 0)
+        .unwrap()
+}
+
+pub fn part_2(input: &str) -> usize {
+    let valley = parse_input(input);
+
+    let there = valley
+        .fastest_crossing(valley.start, valley.goal, 0)
+        .unwrap();
+    let back = valley
+        .fastest_crossing(valley.goal, valley.start, there)
+        .unwrap();
+
+    valley
+        .fastest_crossing(valley.start, valley.goal, back)
+        .unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::day24::{parse_input, part_1, part_2};
+
+    const EXAMPLE_INPUT: &str = "\
+#.######
+#>>.<^<#
+#.<..<<#
+#>v.><>#
+#<^v^^>#
+######.#";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(EXAMPLE_INPUT), 18);
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(EXAMPLE_INPUT), 54);
+    }
+
+    #[test]
+    fn test_parse_input() {
+        let valley = parse_input(EXAMPLE_INPUT);
+
+        assert_eq!((valley.width, valley.height), (6, 4));
+        assert_eq!(valley.start, (1, 0));
+        assert_eq!(valley.goal, (6, 5));
+        assert_eq!(valley.period, 12);
+    }
+}
@@ -0,0 +1,256 @@
+use std::fmt::{Display, Formatter};
+use std::iter::Sum;
+use std::ops::Add;
+use std::str::FromStr;
+
+/// A number in balanced base five, where each digit ranges from minus two (`=`) to two.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Default)]
+pub struct Snafu(i128);
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ParseSnafuError {
+    Empty,
+    InvalidDigit { position: usize, digit: char },
+    Overflow,
+}
+
+impl Display for ParseSnafuError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "cannot parse a SNAFU number from an empty string"),
+            Self::InvalidDigit { position, digit } => {
+                write!(f, "invalid SNAFU digit {digit:?} at position {position}")
+            }
+            Self::Overflow => write!(f, "SNAFU number is too large"),
+        }
+    }
+}
+
+fn digit_value(digit: char) -> Option<i128> {
+    match digit {
+        '2' => Some(2),
+        '1' => Some(1),
+        '0' => Some(0),
+        '-' => Some(-1),
+        '=' => Some(-2),
+        _ => None,
+    }
+}
+
+impl FromStr for Snafu {
+    type Err = ParseSnafuError;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        if string.is_empty() {
+            return Err(ParseSnafuError::Empty);
+        }
+
+        string
+            .chars()
+            .enumerate()
+            .try_fold(0i128, |acc, (position, digit)| {
+                let value =
+                    digit_value(digit).ok_or(ParseSnafuError::InvalidDigit { position, digit })?;
+
+                // `acc * 5` alone can lie just outside the range when the last digit brings the
+                // number back into it, as for `i128::MIN`. Splitting it into `acc * 4 + value + acc`
+                // keeps every step within the range whenever the result is.
+                acc.checked_mul(4)
+                    .and_then(|scaled| scaled.checked_add(value))
+                    .and_then(|scaled| scaled.checked_add(acc))
+                    .ok_or(ParseSnafuError::Overflow)
+            })
+            .map(Self)
+    }
+}
+
+impl Display for Snafu {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.0 == 0 {
+            return write!(f, "0");
+        }
+
+        let mut digits = Vec::new();
+        let mut remaining = self.0;
+
+        // Remainders of three and four are written as `=` and `-` with a carry into the next
+        // digit. Euclidean division keeps this correct for negative numbers and cannot overflow.
+        while remaining != 0 {
+            let remainder = remaining.rem_euclid(5);
+            digits.push(match remainder {
+                0 => '0',
+                1 => '1',
+                2 => '2',
+                3 => '=',
+                4 => '-',
+                _ => unreachable!(),
+            });
+
+            remaining = remaining.div_euclid(5) + i128::from(remainder > 2);
+        }
+
+        write!(f, "{}", String::from_iter(digits.iter().rev()))
+    }
+}
+
+impl Add for Snafu {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self(self.0 + other.0)
+    }
+}
+
+impl Sum for Snafu {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
+
+impl From<i64> for Snafu {
+    fn from(value: i64) -> Self {
+        Self(value as i128)
+    }
+}
+
+impl From<i128> for Snafu {
+    fn from(value: i128) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Snafu> for i128 {
+    fn from(snafu: Snafu) -> Self {
+        snafu.0
+    }
+}
+
+impl TryFrom<Snafu> for i64 {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(snafu: Snafu) -> Result<Self, Self::Error> {
+        i64::try_from(snafu.0)
+    }
+}
+
+fn parse_input(input: &str) -> Vec<Snafu> {
+    input
+        .trim()
+        .lines()
+        .map(|line| line.trim().parse::<Snafu>().unwrap())
+        .collect()
+}
+
+pub fn part_1(input: &str) -> String {
+    parse_input(input).into_iter().sum::<Snafu>().to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::day25::{parse_input, part_1, ParseSnafuError, Snafu};
+    use proptest::prelude::*;
+
+    const EXAMPLE_INPUT: &str = "\
+1=-0-2
+12111
+2=0=
+21
+2=01
+111
+20012
+112
+1=-1=
+1-12
+12
+1=
+122";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(EXAMPLE_INPUT), "2=-1=0");
+    }
+
+    #[test]
+    fn test_parse_input() {
+        let result = parse_input(EXAMPLE_INPUT)
+            .into_iter()
+            .map(i128::from)
+            .collect::<Vec<_>>();
+        let expected = [1747, 906, 198, 11, 201, 31, 1257, 32, 353, 107, 7, 3, 37];
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_display() {
+        let expected = [
+            (1, "1"),
+            (2, "2"),
+            (3, "1="),
+            (4, "1-"),
+            (5, "10"),
+            (10, "20"),
+            (15, "1=0"),
+            (2022, "1=11-2"),
+            (12345, "1-0---0"),
+            (314159265, "1121-1110-1=0"),
+        ];
+
+        for (decimal, snafu) in expected {
+            assert_eq!(Snafu::from(decimal as i64).to_string(), snafu);
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!("".parse::<Snafu>(), Err(ParseSnafuError::Empty));
+        assert_eq!(
+            "1=3".parse::<Snafu>(),
+            Err(ParseSnafuError::InvalidDigit {
+                position: 2,
+                digit: '3'
+            })
+        );
+        assert_eq!(
+            "2".repeat(60).parse::<Snafu>(),
+            Err(ParseSnafuError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_round_trip_limits() {
+        for value in [i128::MIN, i128::MIN + 1, i128::MAX - 1, i128::MAX] {
+            let parsed = Snafu::from(value).to_string().parse::<Snafu>();
+
+            assert_eq!(parsed.map(i128::from), Ok(value));
+        }
+
+        let below_min = Snafu::from(i128::MIN).to_string().replacen('-', "=", 1);
+        assert_eq!(below_min.parse::<Snafu>(), Err(ParseSnafuError::Overflow));
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip_i64(value in any::<i64>()) {
+            let snafu = Snafu::from(value);
+            let parsed = snafu.to_string().parse::<Snafu>().unwrap();
+
+            prop_assert_eq!(parsed, snafu);
+            prop_assert_eq!(i64::try_from(parsed).unwrap(), value);
+        }
+
+        #[test]
+        fn test_round_trip_i128(value in any::<i128>()) {
+            let parsed = Snafu::from(value).to_string().parse::<Snafu>().unwrap();
+
+            prop_assert_eq!(i128::from(parsed), value);
+        }
+
+        #[test]
+        fn test_add(a in any::<i64>(), b in any::<i64>()) {
+            let sum = Snafu::from(a) + Snafu::from(b);
+
+            prop_assert_eq!(i128::from(sum), a as i128 + b as i128);
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use std::fmt::{Display, Formatter};
+
+use crate::day3::priority::PriorityTable;
+use crate::day3::{parse_input, ElfGroups, Grouping, RucksackError};
+
+/// An item type found in both compartments of a rucksack.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct SharedItem {
+    pub item: char,
+    pub first: usize,
+    pub second: usize,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct RucksackAudit {
+    pub line: usize,
+    pub shared: Vec<SharedItem>,
+}
+
+impl RucksackAudit {
+    /// The puzzle assumes exactly one item type ends up in both compartments.
+    pub fn is_violation(&self) -> bool {
+        self.shared.len() != 1
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct GroupAudit {
+    /// The lines of the rucksacks in the group.
+    pub lines: Vec<usize>,
+    pub badge_candidates: Vec<char>,
+}
+
+impl GroupAudit {
+    /// The puzzle assumes exactly one item type is carried by every elf of a group.
+    pub fn is_violation(&self) -> bool {
+        self.badge_candidates.len() != 1
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Audit {
+    pub rucksacks: Vec<RucksackAudit>,
+    pub groups: Vec<GroupAudit>,
+}
+
+impl Audit {
+    pub fn rucksack_violations(&self) -> impl Iterator<Item = &RucksackAudit> {
+        self.rucksacks
+            .iter()
+            .filter(|rucksack| rucksack.is_violation())
+    }
+
+    pub fn group_violations(&self) -> impl Iterator<Item = &GroupAudit> {
+        self.groups.iter().filter(|group| group.is_violation())
+    }
+}
+
+fn count(items: &str, item: char) -> usize {
+    items.chars().filter(|candidate| *candidate == item).count()
+}
+
+/// Audits every rucksack of the input and every group of `group_size` elves. Shared items are
+/// listed in the order of the table's alphabet.
+pub fn audit(
+    input: &str,
+    group_size: usize,
+    table: &PriorityTable,
+) -> Result<Audit, RucksackError> {
+    let rucksacks = parse_input(input);
+    table.validate(&rucksacks)?;
+
+    let rucksack_audits = rucksacks
+        .iter()
+        .map(|rucksack| {
+            let [first, second] = rucksack.compartments()?;
+            let shared = table
+                .items(table.item_mask(first) & table.item_mask(second))
+                .into_iter()
+                .map(|item| SharedItem {
+                    item,
+                    first: count(first, item),
+                    second: count(second, item),
+                })
+                .collect();
+
+            Ok(RucksackAudit {
+                line: rucksack.line,
+                shared,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Validate the group size, then pair the groups back up with their lines.
+    let groups = ElfGroups(group_size).group(&rucksacks)?;
+    let group_audits = groups
+        .iter()
+        .zip(rucksacks.chunks(group_size))
+        .map(|(group, members)| GroupAudit {
+            lines: members.iter().map(|rucksack| rucksack.line).collect(),
+            badge_candidates: table.items(table.common_items(group)),
+        })
+        .collect();
+
+    Ok(Audit {
+        rucksacks: rucksack_audits,
+        groups: group_audits,
+    })
+}
+
+impl Display for Audit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "rucksacks")?;
+        for rucksack in &self.rucksacks {
+            let shared = rucksack
+                .shared
+                .iter()
+                .map(|shared| format!("{} ({}/{})", shared.item, shared.first, shared.second))
+                .collect::<Vec<_>>();
+            let marker = if rucksack.is_violation() { "!" } else { " " };
+
+            writeln!(
+                f,
+                "{marker} line {:>4}: {}",
+                rucksack.line,
+                if shared.is_empty() {
+                    "nothing shared".to_string()
+                } else {
+                    shared.join(", ")
+                }
+            )?;
+        }
+
+        writeln!(f, "\ngroups")?;
+        for group in &self.groups {
+            let lines = group
+                .lines
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            let marker = if group.is_violation() { "!" } else { " " };
+
+            writeln!(
+                f,
+                "{marker} lines {}: {}",
+                lines.join(", "),
+                if group.badge_candidates.is_empty() {
+                    "no badge".to_string()
+                } else {
+                    String::from_iter(&group.badge_candidates)
+                }
+            )?;
+        }
+
+        writeln!(
+            f,
+            "\n{} rucksack and {} group violations",
+            self.rucksack_violations().count(),
+            self.group_violations().count()
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::day3::audit::{audit, SharedItem};
+    use crate::day3::priority::PriorityTable;
+    use crate::day3::RucksackError;
+
+    const EXAMPLE_INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw";
+    const FILE_INPUT: &str = include_str!("input.txt");
+
+    #[test]
+    fn test_audit() {
+        let table = PriorityTable::default();
+        let report = audit(EXAMPLE_INPUT, 3, &table).unwrap();
+
+        assert_eq!(
+            report.rucksacks[0].shared,
+            vec![SharedItem {
+                item: 'p',
+                first: 1,
+                second: 1
+            }]
+        );
+        assert_eq!(report.rucksacks[1].shared[0].item, 'L');
+        assert_eq!(report.rucksacks[1].shared[0].first, 2);
+        assert_eq!(report.groups[0].lines, [1, 2, 3]);
+        assert_eq!(report.groups[0].badge_candidates, ['r']);
+        assert_eq!(report.groups[1].badge_candidates, ['Z']);
+        assert_eq!(report.rucksack_violations().count(), 0);
+        assert_eq!(report.group_violations().count(), 0);
+        assert_eq!(
+            audit(FILE_INPUT, 3, &table)
+                .unwrap()
+                .rucksack_violations()
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_violations() {
+        let report = audit("abcabc\nabcdef\nabxcya\n", 3, &PriorityTable::default()).unwrap();
+        let lines = report
+            .rucksack_violations()
+            .map(|rucksack| rucksack.line)
+            .collect::<Vec<_>>();
+
+        assert_eq!(lines, [1, 2]);
+        assert_eq!(report.groups[0].badge_candidates, ['a', 'b', 'c']);
+        assert!(report.to_string().contains("! line    2: nothing shared"));
+        assert!(report
+            .to_string()
+            .ends_with("2 rucksack and 1 group violations\n"));
+    }
+
+    #[test]
+    fn test_custom_alphabet() {
+        let digits = PriorityTable::from_alphabet("9876543210").unwrap();
+        let report = audit("1212\n3113\n", 2, &digits).unwrap();
+
+        assert_eq!(report.rucksacks[0].shared.len(), 2);
+        assert_eq!(report.rucksacks[0].shared[0].item, '2');
+        assert_eq!(report.groups[0].badge_candidates, ['1']);
+        assert_eq!(
+            audit("1212\n3113\n", 2, &PriorityTable::default()),
+            Err(RucksackError::UnknownItem {
+                line: 1,
+                column: 1,
+                item: '1'
+            })
+        );
+    }
+}
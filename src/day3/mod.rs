@@ -1,135 +1,175 @@
-use std::iter::Zip;
-use std::str::Chars;
-use std::vec;
-
-const LETTERS_LOWERCASE: [char; 26] = [
-    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's',
-    't', 'u', 'v', 'w', 'x', 'y', 'z',
-];
-const LETTERS_UPPERCASE: [char; 26] = [
-    'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S',
-    'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
-];
-
-trait ToVec<T>
-where
-    T: Sized,
-{
-    fn to_vec(self) -> Vec<T>;
-}
+use std::fmt::{Display, Formatter};
 
-impl<T> ToVec<T> for (T, T) {
-    fn to_vec(self) -> Vec<T> {
-        vec![self.0, self.1]
-    }
-}
+use priority::PriorityTable;
 
-impl<T> ToVec<T> for (T, (T, T)) {
-    fn to_vec(self) -> Vec<T> {
-        vec![self.0, self.1 .0, self.1 .1]
-    }
-}
+pub mod audit;
+pub mod priority;
+pub mod reorganize;
+
+/// Every item type is represented by the bit at its position in the alphabet of a
+/// [`PriorityTable`], so the contents of a rucksack fit into a single `u64` and intersecting
+/// rucksacks is a bitwise AND.
+type ItemMask = u64;
 
 type Group<'a> = Vec<&'a str>;
 type Groups<'a> = Vec<Group<'a>>;
 
-fn get_chars<'a>(group: &[&'a str]) -> Zip<Chars<'a>, Zip<Chars<'a>, Chars<'a>>> {
-    group[0].chars().zip(
-        group[1]
-            .chars()
-            .zip(group.get(2).unwrap_or(&group[0]).chars()),
-    )
+#[derive(Clone, PartialEq, Debug)]
+pub enum RucksackError {
+    /// A rucksack whose items cannot be split into two equally sized compartments.
+    OddLength { line: usize, length: usize },
+    /// The number of rucksacks is not a multiple of the group size.
+    IncompleteGroup { rucksacks: usize, group_size: usize },
+    /// A group without any item shared by all of its members.
+    NoCommonItem { group: usize },
+    /// An item outside the alphabet of the priority table, at a column counted from one.
+    UnknownItem {
+        line: usize,
+        column: usize,
+        item: char,
+    },
 }
 
-fn get_priority(letter: &str) -> u8 {
-    let letter = letter.chars().next().unwrap();
-    let find_position = |set: [char; 26]| set.into_iter().position(|char| char == letter);
-
-    1 + if let Some(position) = find_position(LETTERS_LOWERCASE) {
-        position
-    } else {
-        find_position(LETTERS_UPPERCASE).unwrap() + LETTERS_LOWERCASE.len()
-    } as u8
+impl Display for RucksackError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OddLength { line, length } => write!(
+                f,
+                "line {line}: {length} items cannot be split into two compartments"
+            ),
+            Self::IncompleteGroup {
+                rucksacks,
+                group_size,
+            } => write!(
+                f,
+                "{rucksacks} rucksacks cannot be split into groups of {group_size}"
+            ),
+            Self::NoCommonItem { group } => write!(f, "group {group} shares no item"),
+            Self::UnknownItem { line, column, item } => {
+                write!(f, "line {line}, column {column}: unknown item {item:?}")
+            }
+        }
+    }
 }
 
-fn prepare_input_part_1(input: &str) -> Groups<'_> {
-    input
-        .trim()
-        .split('\n')
-        .map(|line| line.split_at(line.len() / 2).to_vec())
-        .collect()
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Rucksack<'a> {
+    /// The line of the input the rucksack was listed on, counted from one.
+    pub line: usize,
+    pub items: &'a str,
 }
 
-fn prepare_input_part_2(input: &str) -> Groups<'_> {
-    input
-        .trim()
-        .split('\n')
-        .collect::<Vec<_>>()
-        .chunks(3)
-        .map(<[_]>::to_vec)
-        .collect()
+impl<'a> Rucksack<'a> {
+    pub fn compartments(&self) -> Result<[&'a str; 2], RucksackError> {
+        let length = self.items.chars().count();
+        if !length.is_multiple_of(2) {
+            return Err(RucksackError::OddLength {
+                line: self.line,
+                length,
+            });
+        }
+
+        // Items outside ASCII take several bytes, so the middle is found by counting chars.
+        let middle = self
+            .items
+            .char_indices()
+            .nth(length / 2)
+            .map_or(self.items.len(), |(index, _)| index);
+        let (first, second) = self.items.split_at(middle);
+        Ok([first, second])
+    }
 }
 
-fn compartment_has_char(compartment: &[&str], char: char) -> Option<char> {
-    let contains = |str: &str| str.chars().any(|c| c == char);
+/// Decides which item lists have to share an item.
+pub trait Grouping {
+    fn group<'a>(&self, rucksacks: &[Rucksack<'a>]) -> Result<Groups<'a>, RucksackError>;
+}
 
-    if compartment.iter().all(|comp| contains(comp)) {
-        return Some(char);
+/// Part one: both compartments of every rucksack form a group.
+pub struct Compartments;
+
+impl Grouping for Compartments {
+    fn group<'a>(&self, rucksacks: &[Rucksack<'a>]) -> Result<Groups<'a>, RucksackError> {
+        rucksacks
+            .iter()
+            .map(|rucksack| {
+                rucksack
+                    .compartments()
+                    .map(|compartments| compartments.to_vec())
+            })
+            .collect()
     }
-
-    None
 }
 
-fn get_common_item(compartment: Group<'_>) -> Option<char> {
-    get_chars(&compartment).find_map(|chars| {
-        chars
-            .to_vec()
-            .into_iter()
-            .find_map(|c| compartment_has_char(&compartment, c))
-    })
+/// Part two: every `n` consecutive rucksacks belong to a group of elves.
+pub struct ElfGroups(pub usize);
+
+impl Grouping for ElfGroups {
+    fn group<'a>(&self, rucksacks: &[Rucksack<'a>]) -> Result<Groups<'a>, RucksackError> {
+        let ElfGroups(size) = *self;
+        if size == 0 || !rucksacks.len().is_multiple_of(size) {
+            return Err(RucksackError::IncompleteGroup {
+                rucksacks: rucksacks.len(),
+                group_size: size,
+            });
+        }
+
+        Ok(rucksacks
+            .chunks(size)
+            .map(|group| group.iter().map(|rucksack| rucksack.items).collect())
+            .collect())
+    }
 }
 
-fn sum_priorities(groups: Groups<'_>, get_char_predicate: fn(Vec<&str>) -> Option<char>) -> u32 {
-    groups.into_iter().fold(0, |acc, compartment| {
-        acc + get_priority(&get_char_predicate(compartment).unwrap().to_string()) as u32
-    })
+fn parse_input(input: &str) -> Vec<Rucksack<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| Rucksack {
+            line: index + 1,
+            items: line.trim(),
+        })
+        .filter(|rucksack| !rucksack.items.is_empty())
+        .collect()
 }
 
-pub fn execute(groups: Groups<'_>) -> u32 {
-    sum_priorities(groups, get_common_item)
+pub fn execute(input: &str, grouping: &impl Grouping) -> Result<u32, RucksackError> {
+    PriorityTable::default().execute(input, grouping)
 }
 
-pub fn part_1(groups: Groups<'_>) -> u32 {
-    execute(prepare_input_part_1(include_str!("input.txt")))
+pub fn part_1(input: &str) -> Result<u32, RucksackError> {
+    execute(input, &Compartments)
 }
 
-pub fn part_2(groups: Groups<'_>) -> u32 {
-    execute(prepare_input_part_2(include_str!("input.txt")))
+pub fn part_2(input: &str) -> Result<u32, RucksackError> {
+    execute(input, &ElfGroups(3))
 }
 
 #[cfg(test)]
 mod test {
-    use crate::day3::{execute, prepare_input_part_1, prepare_input_part_2};
+    use crate::day3::{
+        execute, parse_input, part_1, part_2, Compartments, ElfGroups, Grouping, RucksackError,
+    };
 
     const EXAMPLE_INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw";
     const FILE_INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn test_part_1() {
-        assert_eq!(execute(prepare_input_part_1(EXAMPLE_INPUT)), 157);
-        assert_eq!(execute(prepare_input_part_1(FILE_INPUT)), 7553);
+        assert_eq!(part_1(EXAMPLE_INPUT), Ok(157));
+        assert_eq!(part_1(FILE_INPUT), Ok(7553));
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(execute(prepare_input_part_2(EXAMPLE_INPUT)), 70);
-        assert_eq!(execute(prepare_input_part_2(FILE_INPUT)), 2758);
+        assert_eq!(part_2(EXAMPLE_INPUT), Ok(70));
+        assert_eq!(part_2(FILE_INPUT), Ok(2758));
     }
 
     #[test]
     fn test_prepare_input() {
-        let result = prepare_input_part_1(EXAMPLE_INPUT);
-        let expected = vec![
+        let result = Compartments.group(&parse_input(EXAMPLE_INPUT)).unwrap();
+        let expected = [
             ["vJrwpWtwJgWr", "hcsFMMfFFhFp"],
             ["jqHRNqRjqzjGDLGL", "rsFMfFZSrLrFZsSL"],
             ["PmmdzqPrV", "vPwwTWBwg"],
@@ -140,4 +180,47 @@ mod test {
 
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_group_sizes() {
+        assert_eq!(
+            execute(EXAMPLE_INPUT, &ElfGroups(1)),
+            Ok(3 + 6 + 4 + 2 + 3 + 13)
+        );
+        assert_eq!(execute(EXAMPLE_INPUT, &ElfGroups(2)), Ok(6 + 17 + 33));
+        assert_eq!(
+            ElfGroups(6)
+                .group(&parse_input(EXAMPLE_INPUT))
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            part_1("abab\r\n\nabcab\n"),
+            Err(RucksackError::OddLength { line: 3, length: 5 })
+        );
+        assert_eq!(
+            execute(EXAMPLE_INPUT, &ElfGroups(4)),
+            Err(RucksackError::IncompleteGroup {
+                rucksacks: 6,
+                group_size: 4
+            })
+        );
+        assert_eq!(
+            part_1("abab\nabcd"),
+            Err(RucksackError::NoCommonItem { group: 2 })
+        );
+        assert_eq!(
+            part_1("abab\nab1b"),
+            Err(RucksackError::UnknownItem {
+                line: 2,
+                column: 3,
+                item: '1'
+            })
+        );
+    }
 }
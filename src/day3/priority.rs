@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use crate::day3::{parse_input, Grouping, Groups, ItemMask, Rucksack, RucksackError};
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum AlphabetError {
+    Empty,
+    /// An item listed more than once, at a position counted from one.
+    Duplicate {
+        item: char,
+        position: usize,
+    },
+    /// Item masks have one bit per item type, so an alphabet holds at most 64 items.
+    TooLarge {
+        size: usize,
+    },
+}
+
+impl Display for AlphabetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "the alphabet has no items"),
+            Self::Duplicate { item, position } => {
+                write!(f, "item {item:?} at position {position} is listed twice")
+            }
+            Self::TooLarge { size } => write!(
+                f,
+                "{size} items do not fit into an alphabet of at most {}",
+                ItemMask::BITS
+            ),
+        }
+    }
+}
+
+/// Maps the items of an alphabet to their priorities. Every item also gets the bit of its position
+/// in the alphabet, so groups are still intersected on item masks.
+#[derive(Clone, PartialEq, Debug)]
+pub struct PriorityTable {
+    /// The items and their priorities, in alphabet order.
+    items: Vec<char>,
+    priorities: Vec<u32>,
+    positions: HashMap<char, usize>,
+}
+
+impl Default for PriorityTable {
+    /// The `a`-`z` and `A`-`Z` alphabet of the puzzle, with priorities 1 to 52.
+    fn default() -> Self {
+        Self::from_alphabet(&String::from_iter(('a'..='z').chain('A'..='Z'))).unwrap()
+    }
+}
+
+impl PriorityTable {
+    /// An alphabet whose items have the priorities 1, 2, 3, ... in the order they are listed.
+    pub fn from_alphabet(alphabet: &str) -> Result<Self, AlphabetError> {
+        Self::from_weights(alphabet.chars().zip(1..))
+    }
+
+    /// An alphabet with a custom priority for every item.
+    pub fn from_weights<I>(weights: I) -> Result<Self, AlphabetError>
+    where
+        I: IntoIterator<Item = (char, u32)>,
+    {
+        let mut table = Self {
+            items: Vec::new(),
+            priorities: Vec::new(),
+            positions: HashMap::new(),
+        };
+
+        for (item, priority) in weights {
+            let position = table.priorities.len();
+            if table.positions.insert(item, position).is_some() {
+                return Err(AlphabetError::Duplicate {
+                    item,
+                    position: position + 1,
+                });
+            }
+            table.items.push(item);
+            table.priorities.push(priority);
+        }
+
+        match table.priorities.len() {
+            0 => Err(AlphabetError::Empty),
+            size if size > ItemMask::BITS as usize => Err(AlphabetError::TooLarge { size }),
+            _ => Ok(table),
+        }
+    }
+
+    pub fn priority(&self, item: char) -> Option<u32> {
+        self.positions
+            .get(&item)
+            .map(|position| self.priorities[*position])
+    }
+
+    /// Checks every item of the rucksacks against the alphabet. Columns are counted from one.
+    pub fn validate(&self, rucksacks: &[Rucksack<'_>]) -> Result<(), RucksackError> {
+        for rucksack in rucksacks {
+            if let Some((index, item)) = rucksack
+                .items
+                .chars()
+                .enumerate()
+                .find(|(_, item)| !self.positions.contains_key(item))
+            {
+                return Err(RucksackError::UnknownItem {
+                    line: rucksack.line,
+                    column: index + 1,
+                    item,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The position of an item in the alphabet, counted from zero.
+    pub(crate) fn position(&self, item: char) -> Option<usize> {
+        self.positions.get(&item).copied()
+    }
+
+    /// The item at a position of the alphabet.
+    pub(crate) fn item(&self, position: usize) -> char {
+        self.items[position]
+    }
+
+    /// The mask of the items, ignoring items outside the alphabet.
+    pub(crate) fn item_mask(&self, items: &str) -> ItemMask {
+        items
+            .chars()
+            .filter_map(|item| self.position(item))
+            .fold(0, |mask, position| mask | 1 << position)
+    }
+
+    /// The items shared by every rucksack or compartment of a group, for groups of any size.
+    pub(crate) fn common_items(&self, group: &[&str]) -> ItemMask {
+        group
+            .iter()
+            .map(|items| self.item_mask(items))
+            .reduce(|common, mask| common & mask)
+            .unwrap_or(0)
+    }
+
+    /// All items of a mask, in alphabet order.
+    pub(crate) fn items(&self, mask: ItemMask) -> Vec<char> {
+        self.items
+            .iter()
+            .enumerate()
+            .filter(|(position, _)| mask & 1 << position != 0)
+            .map(|(_, item)| *item)
+            .collect()
+    }
+
+    /// Sums the priority of the shared item of every group. If a group shares several items, the
+    /// one listed first in the alphabet counts.
+    fn sum_priorities(&self, groups: Groups<'_>) -> Result<u32, RucksackError> {
+        groups
+            .iter()
+            .enumerate()
+            .map(|(index, group)| {
+                let common = self.common_items(group);
+
+                (common != 0)
+                    .then(|| self.priorities[common.trailing_zeros() as usize])
+                    .ok_or(RucksackError::NoCommonItem { group: index + 1 })
+            })
+            .sum()
+    }
+
+    pub fn execute(&self, input: &str, grouping: &impl Grouping) -> Result<u32, RucksackError> {
+        let rucksacks = parse_input(input);
+        self.validate(&rucksacks)?;
+        self.sum_priorities(grouping.group(&rucksacks)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::day3::priority::{AlphabetError, PriorityTable};
+    use crate::day3::{Compartments, ElfGroups, RucksackError};
+
+    #[test]
+    fn test_default_table() {
+        let table = PriorityTable::default();
+
+        assert_eq!(table.priority('a'), Some(1));
+        assert_eq!(table.priority('z'), Some(26));
+        assert_eq!(table.priority('A'), Some(27));
+        assert_eq!(table.priority('Z'), Some(52));
+        assert_eq!(table.priority('1'), None);
+    }
+
+    #[test]
+    fn test_item_masks() {
+        let table = PriorityTable::default();
+
+        assert_eq!(table.item_mask("aAa"), 1 | 1 << 26);
+        assert_eq!(table.common_items(&["abc", "bcd", "cde", "xcy"]), 1 << 2);
+        assert_eq!(table.common_items(&["ab", "cd"]), 0);
+        assert_eq!(table.items(table.item_mask("zZaA")), ['a', 'z', 'A', 'Z']);
+
+        let reversed = PriorityTable::from_alphabet("zyx").unwrap();
+        assert_eq!(reversed.items(reversed.item_mask("xz")), ['z', 'x']);
+        assert_eq!(reversed.position('x'), Some(2));
+    }
+
+    #[test]
+    fn test_custom_alphabets() {
+        let digits = PriorityTable::from_alphabet("0123456789").unwrap();
+        assert_eq!(digits.execute("1221\n3443\n", &Compartments), Ok(2 + 4));
+        assert_eq!(digits.execute("1231\n3443\n", &ElfGroups(2)), Ok(4));
+
+        let symbols = PriorityTable::from_weights([('🍎', 10), ('🍐', 20), ('🍒', 5)]).unwrap();
+        assert_eq!(symbols.execute("🍎🍐🍒🍐", &Compartments), Ok(20));
+        assert_eq!(
+            symbols.execute("🍎🍐🍒🍐\n🍎🍋🍎🍐", &Compartments),
+            Err(RucksackError::UnknownItem {
+                line: 2,
+                column: 2,
+                item: '🍋'
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_alphabets() {
+        assert_eq!(PriorityTable::from_alphabet(""), Err(AlphabetError::Empty));
+        assert_eq!(
+            PriorityTable::from_alphabet("abcb"),
+            Err(AlphabetError::Duplicate {
+                item: 'b',
+                position: 4
+            })
+        );
+        assert_eq!(
+            PriorityTable::from_alphabet(&String::from_iter('!'..='~')),
+            Err(AlphabetError::TooLarge { size: 94 })
+        );
+    }
+}
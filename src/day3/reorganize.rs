@@ -0,0 +1,346 @@
+use std::fmt::{Display, Formatter};
+
+use crate::day3::priority::PriorityTable;
+use crate::day3::{parse_input, ItemMask, Rucksack, RucksackError};
+
+/// Item counts indexed by the position of each item in the alphabet.
+type ItemCounts = [usize; ItemMask::BITS as usize];
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum ReorganizeError {
+    Rucksack(RucksackError),
+    /// An item type with more copies than a compartment can hold, so it always ends up in both.
+    Overfull {
+        line: usize,
+        item: char,
+        count: usize,
+        capacity: usize,
+    },
+    /// No selection of item types fills a compartment exactly.
+    Indivisible {
+        line: usize,
+    },
+}
+
+impl From<RucksackError> for ReorganizeError {
+    fn from(error: RucksackError) -> Self {
+        Self::Rucksack(error)
+    }
+}
+
+impl Display for ReorganizeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rucksack(error) => write!(f, "{error}"),
+            Self::Overfull {
+                line,
+                item,
+                count,
+                capacity,
+            } => write!(
+                f,
+                "line {line}: {count} copies of {item} do not fit into a compartment of {capacity}"
+            ),
+            Self::Indivisible { line } => write!(
+                f,
+                "line {line}: the item types cannot be split into two equal compartments"
+            ),
+        }
+    }
+}
+
+/// Exchanges the items at two positions of a rucksack, counted from zero. `first` is always in the
+/// first compartment and `second` in the second one.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Swap {
+    pub first: usize,
+    pub second: usize,
+    pub items: (char, char),
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Plan {
+    pub line: usize,
+    pub swaps: Vec<Swap>,
+    /// The items after all swaps have been made.
+    pub reorganized: String,
+    /// The summed priorities of the item types that were in both compartments before.
+    pub saved: u32,
+}
+
+fn count_items(items: &str, table: &PriorityTable) -> ItemCounts {
+    let mut counts = [0; ItemMask::BITS as usize];
+    for position in items.chars().filter_map(|item| table.position(item)) {
+        counts[position] += 1;
+    }
+    counts
+}
+
+/// Plans the fewest swaps that leave no item type in both compartments of a rucksack.
+///
+/// Every item type ends up entirely in one compartment, so a plan is a choice of item types whose
+/// copies exactly fill the first compartment. Each copy of such a type that currently sits in the
+/// second compartment has to be swapped over, which makes this a knapsack over the compartment
+/// size that minimises those copies. Swap positions count items, not bytes.
+pub fn plan(rucksack: &Rucksack<'_>, table: &PriorityTable) -> Result<Plan, ReorganizeError> {
+    table.validate(std::slice::from_ref(rucksack))?;
+    let [first, second] = rucksack.compartments()?;
+    let capacity = first.chars().count();
+    let (first_counts, second_counts) = (count_items(first, table), count_items(second, table));
+
+    // Item types are identified by their position in the alphabet.
+    let types = (0..ItemMask::BITS as usize)
+        .filter(|position| first_counts[*position] + second_counts[*position] > 0)
+        .collect::<Vec<_>>();
+
+    if let Some(position) = types
+        .iter()
+        .find(|position| first_counts[**position] + second_counts[**position] > capacity)
+    {
+        return Err(ReorganizeError::Overfull {
+            line: rucksack.line,
+            item: table.item(*position),
+            count: first_counts[*position] + second_counts[*position],
+            capacity,
+        });
+    }
+
+    // costs[i][size] is the fewest swaps with which the first i types fill `size` items of the
+    // first compartment.
+    let mut costs = vec![vec![None; capacity + 1]];
+    costs[0][0] = Some(0);
+    for position in &types {
+        let total = first_counts[*position] + second_counts[*position];
+        let previous = costs.last().unwrap();
+        let next = (0..=capacity)
+            .map(|size| {
+                let keep_out = previous[size];
+                let take_in = size
+                    .checked_sub(total)
+                    .and_then(|rest| previous[rest])
+                    .map(|cost: usize| cost + second_counts[*position]);
+
+                match (keep_out, take_in) {
+                    (Some(a), Some(b)) => Some(a.min(b)),
+                    (a, b) => a.or(b),
+                }
+            })
+            .collect();
+        costs.push(next);
+    }
+
+    if costs[types.len()][capacity].is_none() {
+        return Err(ReorganizeError::Indivisible {
+            line: rucksack.line,
+        });
+    }
+
+    let mut in_first = [false; ItemMask::BITS as usize];
+    let mut size = capacity;
+    for (index, position) in types.iter().enumerate().rev() {
+        if costs[index][size] != costs[index + 1][size] {
+            in_first[*position] = true;
+            size -= first_counts[*position] + second_counts[*position];
+        }
+    }
+
+    let stays_first = |item: &char| {
+        table
+            .position(*item)
+            .is_some_and(|position| in_first[position])
+    };
+    let leaving_first = first
+        .chars()
+        .enumerate()
+        .filter(|(_, item)| !stays_first(item));
+    let leaving_second = second
+        .chars()
+        .enumerate()
+        .filter(|(_, item)| stays_first(item));
+    let swaps = leaving_first
+        .zip(leaving_second)
+        .map(|((first, a), (second, b))| Swap {
+            first,
+            second: capacity + second,
+            items: (a, b),
+        })
+        .collect::<Vec<_>>();
+
+    let mut reorganized = rucksack.items.chars().collect::<Vec<_>>();
+    for swap in &swaps {
+        reorganized.swap(swap.first, swap.second);
+    }
+
+    let saved = table
+        .items(table.item_mask(first) & table.item_mask(second))
+        .into_iter()
+        .filter_map(|item| table.priority(item))
+        .sum();
+
+    Ok(Plan {
+        line: rucksack.line,
+        swaps,
+        reorganized: reorganized.into_iter().collect(),
+        saved,
+    })
+}
+
+/// The plans for every rucksack of an input, or why a rucksack cannot be reorganized.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Reorganization {
+    pub plans: Vec<Result<Plan, ReorganizeError>>,
+}
+
+impl Reorganization {
+    pub fn swap_count(&self) -> usize {
+        self.plans
+            .iter()
+            .flatten()
+            .map(|plan| plan.swaps.len())
+            .sum()
+    }
+
+    /// The priorities saved across all rucksacks that can be reorganized.
+    pub fn saved(&self) -> u32 {
+        self.plans.iter().flatten().map(|plan| plan.saved).sum()
+    }
+
+    pub fn impossible(&self) -> impl Iterator<Item = &ReorganizeError> {
+        self.plans.iter().filter_map(|plan| plan.as_ref().err())
+    }
+}
+
+pub fn reorganize(input: &str, table: &PriorityTable) -> Reorganization {
+    Reorganization {
+        plans: parse_input(input)
+            .iter()
+            .map(|rucksack| plan(rucksack, table))
+            .collect(),
+    }
+}
+
+impl Display for Reorganization {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for plan in &self.plans {
+            match plan {
+                Ok(plan) => {
+                    let swaps = plan
+                        .swaps
+                        .iter()
+                        .map(|swap| {
+                            format!(
+                                "{}@{} <-> {}@{}",
+                                swap.items.0, swap.first, swap.items.1, swap.second
+                            )
+                        })
+                        .collect::<Vec<_>>();
+
+                    writeln!(
+                        f,
+                        "line {:>4}: {} swaps, saves {:>3}  {}",
+                        plan.line,
+                        plan.swaps.len(),
+                        plan.saved,
+                        swaps.join(", ")
+                    )?;
+                }
+                Err(error) => writeln!(f, "{error}")?,
+            }
+        }
+
+        writeln!(
+            f,
+            "\n{} swaps save {} priority",
+            self.swap_count(),
+            self.saved()
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::day3::priority::PriorityTable;
+    use crate::day3::reorganize::{plan, reorganize, ReorganizeError, Swap};
+    use crate::day3::{part_1, Rucksack};
+
+    const EXAMPLE_INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw";
+    const FILE_INPUT: &str = include_str!("input.txt");
+
+    fn rucksack(items: &str) -> Rucksack<'_> {
+        Rucksack { line: 1, items }
+    }
+
+    #[test]
+    fn test_plan() {
+        let plan = plan(&rucksack("abab"), &PriorityTable::default()).unwrap();
+
+        assert_eq!(
+            plan.swaps,
+            vec![Swap {
+                first: 1,
+                second: 2,
+                items: ('b', 'a')
+            }]
+        );
+        assert_eq!(plan.reorganized, "aabb");
+        assert_eq!(plan.saved, 3);
+    }
+
+    #[test]
+    fn test_impossible() {
+        assert_eq!(
+            plan(&rucksack("aaab"), &PriorityTable::default()),
+            Err(ReorganizeError::Overfull {
+                line: 1,
+                item: 'a',
+                count: 3,
+                capacity: 2
+            })
+        );
+        assert_eq!(
+            plan(&rucksack("abcabc"), &PriorityTable::default()),
+            Err(ReorganizeError::Indivisible { line: 1 })
+        );
+    }
+
+    #[test]
+    fn test_reorganize() {
+        let table = PriorityTable::default();
+        let example = reorganize(EXAMPLE_INPUT, &table);
+        assert_eq!(example.plans[0].as_ref().unwrap().swaps.len(), 1);
+        assert_eq!(example.saved(), part_1(EXAMPLE_INPUT).unwrap());
+
+        for reorganization in [example, reorganize(FILE_INPUT, &table)] {
+            for plan in reorganization.plans.iter().flatten() {
+                let (first, second) = plan.reorganized.split_at(plan.reorganized.len() / 2);
+                assert_eq!(table.item_mask(first) & table.item_mask(second), 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom_alphabet() {
+        let symbols = PriorityTable::from_weights([('🍎', 10), ('🍐', 20), ('🍒', 5)]).unwrap();
+        let plan = plan(&rucksack("🍎🍐🍐🍎"), &symbols).unwrap();
+
+        assert_eq!(
+            plan.swaps,
+            vec![Swap {
+                first: 1,
+                second: 3,
+                items: ('🍐', '🍎')
+            }]
+        );
+        assert_eq!(plan.reorganized, "🍎🍎🍐🍐");
+        assert_eq!(plan.saved, 30);
+        assert_eq!(
+            reorganize("🍎🍒🍒🍒", &symbols).plans,
+            [Err(ReorganizeError::Overfull {
+                line: 1,
+                item: '🍒',
+                count: 3,
+                capacity: 2
+            })]
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use crate::day4::range::SectionRange;
+use crate::day4::{elves, prepare_input, AssignmentGroup, Elf};
+
+/// How many elves clean each section between the first and the last assigned section.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Coverage {
+    /// Consecutive ranges of equal depth in section order. Gaps have a depth of zero.
+    pub segments: Vec<(SectionRange, usize)>,
+    /// Elves whose sections are all cleaned by somebody else as well. Each of them could be
+    /// reassigned on their own, but two elves with the same assignment are both listed.
+    pub redundant: Vec<Elf>,
+}
+
+impl Coverage {
+    /// Sections between the first and the last assigned section that nobody cleans.
+    pub fn uncovered(&self) -> Vec<SectionRange> {
+        self.segments
+            .iter()
+            .filter(|(_, depth)| *depth == 0)
+            .map(|(range, _)| *range)
+            .collect()
+    }
+
+    pub fn max_depth(&self) -> usize {
+        self.segments
+            .iter()
+            .map(|(_, depth)| *depth)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The sections cleaned by the most elves.
+    pub fn deepest(&self) -> Vec<SectionRange> {
+        let max_depth = self.max_depth();
+        self.segments
+            .iter()
+            .filter(|(_, depth)| max_depth > 0 && *depth == max_depth)
+            .map(|(range, _)| *range)
+            .collect()
+    }
+
+    /// The covered sections as disjoint ranges that neither overlap nor touch.
+    pub fn merged(&self) -> Vec<SectionRange> {
+        let mut merged: Vec<SectionRange> = Vec::new();
+        for (range, _) in self.segments.iter().filter(|(_, depth)| *depth > 0) {
+            let union = merged.last().and_then(|last| last.union_if_adjacent(range));
+            match (union, merged.last_mut()) {
+                (Some(union), Some(last)) => *last = union,
+                _ => merged.push(*range),
+            }
+        }
+
+        merged
+    }
+}
+
+/// Sweeps over the start and end of every assignment in section order.
+pub fn analyze(elves: &[Elf]) -> Coverage {
+    // An assignment adds one to the depth at its start and removes it right after its end.
+    let mut events = elves
+        .iter()
+        .flat_map(|elf| {
+            [
+                (elf.assignment.start() as i64, 1),
+                (elf.assignment.end() as i64 + 1, -1),
+            ]
+        })
+        .collect::<Vec<(i64, i64)>>();
+    events.sort_unstable();
+
+    let mut segments = Vec::new();
+    let mut depth = 0;
+    for (index, (section, change)) in events.iter().enumerate() {
+        depth += change;
+
+        if let Some((next, _)) = events.get(index + 1) {
+            if next > section {
+                let range = SectionRange::new(*section as i32, (next - 1) as i32);
+                segments.push((range, depth as usize));
+            }
+        }
+    }
+
+    // An elf is redundant if none of its sections is cleaned by them alone.
+    let exclusive = segments
+        .iter()
+        .filter(|(_, depth)| *depth == 1)
+        .map(|(range, _)| *range)
+        .collect::<Vec<_>>();
+    let redundant = elves
+        .iter()
+        .filter(|elf| {
+            let first = exclusive.partition_point(|range| range.end() < elf.assignment.start());
+            exclusive
+                .get(first)
+                .is_none_or(|range| !range.overlaps(&elf.assignment))
+        })
+        .copied()
+        .collect();
+
+    Coverage {
+        segments,
+        redundant,
+    }
+}
+
+pub fn coverage(groups: &[AssignmentGroup]) -> Coverage {
+    analyze(&elves(groups))
+}
+
+pub fn coverage_of_input(input: &str) -> Coverage {
+    coverage(&prepare_input(input))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::day4::coverage::coverage_of_input;
+    use crate::day4::range::SectionRange;
+    use crate::day4::{elves, prepare_input};
+
+    const EXAMPLE_INPUT: &str = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8";
+    const FILE_INPUT: &str = include_str!("input.txt");
+
+    fn range(start: i32, end: i32) -> SectionRange {
+        SectionRange::new(start, end)
+    }
+
+    #[test]
+    fn test_example() {
+        let coverage = coverage_of_input(EXAMPLE_INPUT);
+
+        assert_eq!(coverage.segments.first(), Some(&(range(2, 2), 4)));
+        assert_eq!(coverage.uncovered(), []);
+        assert_eq!(coverage.merged(), [range(2, 9)]);
+        assert_eq!(coverage.max_depth(), 8);
+        assert_eq!(coverage.deepest(), [range(6, 6)]);
+        assert_eq!(
+            coverage
+                .redundant
+                .iter()
+                .map(|elf| (elf.line, elf.position))
+                .collect::<Vec<_>>(),
+            [
+                (1, 1),
+                (1, 2),
+                (2, 1),
+                (2, 2),
+                (3, 1),
+                (4, 1),
+                (4, 2),
+                (5, 1),
+                (5, 2),
+                (6, 1),
+                (6, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_gaps() {
+        let coverage = coverage_of_input("1-3,8-9\n2-4,12-12\n9-10,3-3\n");
+
+        assert_eq!(coverage.uncovered(), [range(5, 7), range(11, 11)]);
+        assert_eq!(
+            coverage.merged(),
+            [range(1, 4), range(8, 10), range(12, 12)]
+        );
+        assert_eq!(coverage.max_depth(), 3);
+        assert_eq!(coverage.deepest(), [range(3, 3)]);
+        assert_eq!(
+            coverage
+                .redundant
+                .iter()
+                .map(|elf| elf.assignment)
+                .collect::<Vec<_>>(),
+            [range(3, 3)]
+        );
+    }
+
+    #[test]
+    fn test_segments_match_brute_force() {
+        let coverage = coverage_of_input(FILE_INPUT);
+        let elves = elves(&prepare_input(FILE_INPUT));
+
+        for (segment, depth) in &coverage.segments {
+            for section in [segment.start(), segment.end()] {
+                let expected = elves
+                    .iter()
+                    .filter(|elf| elf.assignment.contains(section))
+                    .count();
+                assert_eq!(*depth, expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,391 @@
+use std::cmp::{Ordering, Reverse};
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use crate::day4::range::SectionRange;
+use crate::day4::{elves, prepare_input, Elf};
+
+/// Orders elves by the start of their assignment, breaking ties so that every elf is unique.
+type Key = (i32, i32, usize, usize);
+
+fn key(elf: &Elf) -> Key {
+    (
+        elf.assignment.start(),
+        elf.assignment.end(),
+        elf.line,
+        elf.position,
+    )
+}
+
+struct Node {
+    elf: Elf,
+    /// Heap priority of the treap, derived from the elf so indexes are reproducible.
+    priority: u64,
+    /// The assignments that contain the start of this node's assignment while those of all
+    /// ancestors lie outside of them, once sorted by start and once by descending end.
+    by_start: Vec<Elf>,
+    by_end: Vec<Elf>,
+    left: Option<Box<Node>>,
+    right: Option<Box<Node>>,
+}
+
+impl Node {
+    fn new(elf: Elf) -> Box<Self> {
+        let mut hasher = DefaultHasher::new();
+        elf.hash(&mut hasher);
+
+        Box::new(Self {
+            elf,
+            priority: hasher.finish(),
+            by_start: Vec::new(),
+            by_end: Vec::new(),
+            left: None,
+            right: None,
+        })
+    }
+
+    fn center(&self) -> i32 {
+        self.elf.assignment.start()
+    }
+
+    fn child(&mut self, left: bool) -> &mut Option<Box<Node>> {
+        if left {
+            &mut self.left
+        } else {
+            &mut self.right
+        }
+    }
+
+    fn store(&mut self, elves: impl IntoIterator<Item = Elf> + Clone) {
+        // Both lists stay sorted runs, which the sort merges in linear time.
+        self.by_start.extend(elves.clone());
+        self.by_start.sort_by_key(key);
+        self.by_end.extend(elves);
+        self.by_end
+            .sort_by_key(|elf| (Reverse(elf.assignment.end()), key(elf)));
+    }
+
+    fn unstore(&mut self, moves: impl Fn(&Elf) -> bool) -> Vec<Elf> {
+        let moved = self
+            .by_start
+            .iter()
+            .filter(|elf| moves(elf))
+            .copied()
+            .collect();
+        self.by_start.retain(|elf| !moves(elf));
+        self.by_end.retain(|elf| !moves(elf));
+        moved
+    }
+}
+
+/// Lifts the left or right child of a node above it. The assignments stored at the node that also
+/// contain the center of the child are now first reached at the child, so they move along.
+fn rotate(node: &mut Box<Node>, left: bool) {
+    let Some(mut child) = node.child(left).take() else {
+        return;
+    };
+    *node.child(left) = child.child(!left).take();
+
+    let center = child.center();
+    let moved = node.unstore(|elf| elf.assignment.contains(center));
+    std::mem::swap(node, &mut child);
+    *node.child(!left) = Some(child);
+    node.store(moved);
+}
+
+/// Adds a leaf for an elf that is not indexed yet and rotates it up to its place in the treap.
+fn insert(slot: &mut Option<Box<Node>>, elf: Elf) {
+    let Some(node) = slot else {
+        *slot = Some(Node::new(elf));
+        return;
+    };
+
+    let left = key(&elf) < key(&node.elf);
+    let priority = node.priority;
+    insert(node.child(left), elf);
+    if node
+        .child(left)
+        .as_ref()
+        .is_some_and(|child| child.priority > priority)
+    {
+        rotate(node, left);
+    }
+}
+
+/// Rotates the node with the given key down to a leaf and drops it. Its own assignment has to be
+/// unstored first.
+fn remove(slot: &mut Option<Box<Node>>, removed: &Key) {
+    let Some(node) = slot else {
+        return;
+    };
+
+    match removed.cmp(&key(&node.elf)) {
+        Ordering::Less => remove(&mut node.left, removed),
+        Ordering::Greater => remove(&mut node.right, removed),
+        Ordering::Equal => {
+            let left = match (&node.left, &node.right) {
+                (None, None) => None,
+                (Some(left), Some(right)) => Some(left.priority > right.priority),
+                (left, _) => Some(left.is_some()),
+            };
+
+            match left {
+                Some(left) => {
+                    rotate(node, left);
+                    remove(node.child(!left), removed);
+                }
+                None => *slot = None,
+            }
+        }
+    }
+}
+
+/// A centered interval tree over the assignments of a camp. Its nodes form a treap ordered by
+/// assignment, and every assignment is stored at the highest node whose start it contains.
+///
+/// A stabbing query walks a single path of the treap and reads the sorted lists of every node on
+/// it only as far as they match, which takes O(log n + k) for k matches with the treap's expected
+/// O(log n) height. A range query adds the assignments that start inside the range through an
+/// ordinary range search of the treap, which has the same cost. Updates take an expected constant
+/// number of rotations, each of which re-sorts the lists of the two nodes involved.
+#[derive(Default)]
+pub struct AssignmentIndex {
+    root: Option<Box<Node>>,
+    len: usize,
+}
+
+impl AssignmentIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_input(input: &str) -> Self {
+        elves(&prepare_input(input)).into_iter().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn contains(&self, elf: &Elf) -> bool {
+        let key = key(elf);
+        let mut node = &self.root;
+        while let Some(current) = node {
+            node = match key.cmp(&self::key(&current.elf)) {
+                Ordering::Less => &current.left,
+                Ordering::Greater => &current.right,
+                Ordering::Equal => return true,
+            };
+        }
+        false
+    }
+
+    /// The highest node whose start lies in the assignment. It exists for every indexed elf, as
+    /// their own node qualifies.
+    fn home(&mut self, elf: &Elf) -> Option<&mut Box<Node>> {
+        let mut node = self.root.as_mut();
+        while let Some(current) = node {
+            if current.center() < elf.assignment.start() {
+                node = current.right.as_mut();
+            } else if current.center() > elf.assignment.end() {
+                node = current.left.as_mut();
+            } else {
+                return Some(current);
+            }
+        }
+        None
+    }
+
+    /// Adds an elf, returning `false` if they were already indexed.
+    pub fn insert(&mut self, elf: Elf) -> bool {
+        if self.contains(&elf) {
+            return false;
+        }
+
+        insert(&mut self.root, elf);
+        if let Some(home) = self.home(&elf) {
+            home.store([elf]);
+        }
+        self.len += 1;
+        true
+    }
+
+    /// Removes an elf, returning `false` if they were not indexed.
+    pub fn remove(&mut self, elf: &Elf) -> bool {
+        if !self.contains(elf) {
+            return false;
+        }
+
+        if let Some(home) = self.home(elf) {
+            home.unstore(|other| other == elf);
+        }
+        remove(&mut self.root, &key(elf));
+        self.len -= 1;
+        true
+    }
+
+    /// The elves assigned to a section, in no particular order.
+    pub fn stab(&self, section: i32) -> Vec<Elf> {
+        let mut found = Vec::new();
+        let mut node = &self.root;
+
+        // Assignments stored below a node lie entirely on one side of its center, so only the
+        // side of the section can hold more matches.
+        while let Some(current) = node {
+            match section.cmp(&current.center()) {
+                Ordering::Less => {
+                    found.extend(
+                        current
+                            .by_start
+                            .iter()
+                            .take_while(|elf| elf.assignment.start() <= section),
+                    );
+                    node = &current.left;
+                }
+                Ordering::Greater => {
+                    found.extend(
+                        current
+                            .by_end
+                            .iter()
+                            .take_while(|elf| elf.assignment.end() >= section),
+                    );
+                    node = &current.right;
+                }
+                Ordering::Equal => {
+                    found.extend(&current.by_start);
+                    break;
+                }
+            }
+        }
+
+        found
+    }
+
+    /// The elves whose assignment shares a section with the range, in no particular order.
+    pub fn overlapping(&self, range: &SectionRange) -> Vec<Elf> {
+        // An overlapping assignment either contains the start of the range or starts after it.
+        let mut found = self.stab(range.start());
+        collect_starts(&self.root, range, &mut found);
+        found
+    }
+}
+
+/// Collects the elves whose assignment starts after the start of the range but within it.
+fn collect_starts(node: &Option<Box<Node>>, range: &SectionRange, found: &mut Vec<Elf>) {
+    let Some(node) = node else {
+        return;
+    };
+
+    if node.center() > range.start() {
+        collect_starts(&node.left, range, found);
+        if node.center() <= range.end() {
+            found.push(node.elf);
+        }
+    }
+    if node.center() <= range.end() {
+        collect_starts(&node.right, range, found);
+    }
+}
+
+impl FromIterator<Elf> for AssignmentIndex {
+    fn from_iter<T: IntoIterator<Item = Elf>>(iter: T) -> Self {
+        let mut index = Self::new();
+        for elf in iter {
+            index.insert(elf);
+        }
+        index
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::day4::index::AssignmentIndex;
+    use crate::day4::range::SectionRange;
+    use crate::day4::{elves, prepare_input, Elf};
+
+    const EXAMPLE_INPUT: &str = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8";
+    const FILE_INPUT: &str = include_str!("input.txt");
+
+    /// Queries return elves in no particular order, so they are compared by assignment.
+    fn sorted(mut elves: Vec<Elf>) -> Vec<Elf> {
+        elves.sort_by_key(|elf| (elf.assignment, elf.line, elf.position));
+        elves
+    }
+
+    fn ids(elves: &[Elf]) -> Vec<(usize, usize)> {
+        sorted(elves.to_vec())
+            .iter()
+            .map(|elf| (elf.line, elf.position))
+            .collect()
+    }
+
+    #[test]
+    fn test_queries() {
+        let index = AssignmentIndex::from_input(EXAMPLE_INPUT);
+
+        assert_eq!(index.len(), 12);
+        assert_eq!(ids(&index.stab(9)), [(3, 2)]);
+        assert_eq!(ids(&index.stab(1)), []);
+        assert_eq!(
+            ids(&index.stab(3)),
+            [(2, 1), (1, 1), (6, 1), (4, 1), (4, 2)]
+        );
+        assert_eq!(
+            ids(&index.overlapping(&SectionRange::new(8, 12))),
+            [(4, 1), (6, 2), (1, 2), (3, 2)]
+        );
+    }
+
+    #[test]
+    fn test_updates() {
+        let elves = elves(&prepare_input(EXAMPLE_INPUT));
+        let mut index = elves.iter().copied().collect::<AssignmentIndex>();
+
+        assert!(!index.insert(elves[0]));
+        assert!(index.remove(&elves[5]));
+        assert!(!index.remove(&elves[5]));
+        assert_eq!(index.len(), 11);
+        assert_eq!(ids(&index.stab(9)), []);
+
+        let moved = Elf {
+            assignment: SectionRange::new(9, 12),
+            ..elves[5]
+        };
+        assert!(index.insert(moved));
+        assert_eq!(ids(&index.stab(12)), [(3, 2)]);
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let elves = elves(&prepare_input(FILE_INPUT));
+        let mut index = elves.iter().copied().collect::<AssignmentIndex>();
+        for elf in elves.iter().step_by(3) {
+            index.remove(elf);
+        }
+        let remaining = elves
+            .iter()
+            .enumerate()
+            .filter(|(position, _)| position % 3 != 0)
+            .map(|(_, elf)| *elf)
+            .collect::<Vec<_>>();
+
+        let ranges = (0..=100)
+            .map(|section| SectionRange::new(section, section))
+            .chain([SectionRange::new(30, 45), SectionRange::new(1, 99)]);
+        for range in ranges {
+            let expected = remaining
+                .iter()
+                .filter(|elf| elf.assignment.overlaps(&range))
+                .copied()
+                .collect::<Vec<_>>();
+
+            assert_eq!(sorted(index.overlapping(&range)), sorted(expected.clone()));
+            if range.start() == range.end() {
+                assert_eq!(sorted(index.stab(range.start())), sorted(expected));
+            }
+        }
+    }
+}
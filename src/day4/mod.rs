@@ -1,9 +1,33 @@
+use std::cmp::Reverse;
+
+use range::SectionRange;
+
+pub mod coverage;
+pub mod index;
+pub mod range;
+pub mod schedule;
+
 enum Intersection {
     Overlap,
     Includes,
 }
 
-type Assignment = (i32, i32);
+impl Intersection {
+    /// Whether any two assignments of a group relate in this way. Wider assignments come first,
+    /// so a range can only include the ones after it.
+    fn holds(&self, group: &AssignmentGroup) -> bool {
+        let sorted = sort_assignment_group(group);
+
+        sorted.iter().enumerate().any(|(index, wider)| {
+            sorted[index + 1..].iter().any(|narrower| match self {
+                Self::Overlap => wider.overlaps(narrower),
+                Self::Includes => wider.includes(narrower),
+            })
+        })
+    }
+}
+
+type Assignment = SectionRange;
 type AssignmentGroup = Vec<Assignment>;
 
 fn prepare_input(input: &str) -> Vec<AssignmentGroup> {
@@ -13,24 +37,41 @@ fn prepare_input(input: &str) -> Vec<AssignmentGroup> {
         .map(|assignment_pair| {
             assignment_pair
                 .split(',')
-                .map(|assignment| {
-                    let parts = assignment
-                        .split('-')
-                        .map(|id| id.parse::<i32>().unwrap())
-                        .collect::<Vec<_>>();
+                .map(|assignment| assignment.parse().unwrap())
+                .collect()
+        })
+        .collect()
+}
+
+/// A single elf's assignment, identified by the line of its group and its position within that
+/// group, both counted from one.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Elf {
+    pub line: usize,
+    pub position: usize,
+    pub assignment: SectionRange,
+}
 
-                    (parts[0], parts[1])
+pub fn elves(groups: &[AssignmentGroup]) -> Vec<Elf> {
+    groups
+        .iter()
+        .enumerate()
+        .flat_map(|(line, group)| {
+            group
+                .iter()
+                .enumerate()
+                .map(move |(position, assignment)| Elf {
+                    line: line + 1,
+                    position: position + 1,
+                    assignment: *assignment,
                 })
-                .collect()
         })
         .collect()
 }
 
 fn sort_assignment_group(group: &AssignmentGroup) -> AssignmentGroup {
     let mut sorted = group.clone();
-    sorted.sort_by(|(id_1_1, id_1_2), (id_2_1, id_2_2)| {
-        (id_2_1 - id_2_2).abs().cmp(&(id_1_1 - id_1_2).abs())
-    });
+    sorted.sort_by_key(|assignment| Reverse(assignment.len()));
 
     sorted
 }
@@ -41,23 +82,54 @@ fn check_for_intersection(
 ) -> usize {
     assignments
         .into_iter()
-        .filter(|assignment_group| {
-            let assignment_group = sort_assignment_group(assignment_group);
-            let [(first_start, first_end), (second_start, second_end)]: &[_; 2] =
-                assignment_group.as_slice().try_into().unwrap();
-
-            let first_set = *first_start..=*first_end;
-            let mut second_set = *second_start..=*second_end;
-
-            let contains_id = |id: i32| first_set.contains(&id);
-            match intersection_type {
-                Intersection::Overlap => second_set.any(contains_id),
-                Intersection::Includes => second_set.all(contains_id),
-            }
-        })
+        .filter(|assignment_group| intersection_type.holds(assignment_group))
         .count()
 }
 
+/// How the assignments of a group of any size relate to each other.
+#[derive(Clone, PartialEq, Debug)]
+pub struct GroupReport {
+    /// Whether some assignment includes another one.
+    pub includes: bool,
+    /// Whether some two assignments overlap.
+    pub overlaps: bool,
+    /// The sections every elf of the group is assigned to.
+    pub common: Option<SectionRange>,
+    /// The positions of every overlapping pair of assignments, counted from one.
+    pub conflicts: Vec<(usize, usize)>,
+}
+
+pub fn analyze_group(group: &AssignmentGroup) -> GroupReport {
+    let conflicts = group
+        .iter()
+        .enumerate()
+        .flat_map(|(first, a)| {
+            group
+                .iter()
+                .enumerate()
+                .skip(first + 1)
+                .filter(move |(_, b)| a.overlaps(b))
+                .map(move |(second, _)| (first + 1, second + 1))
+        })
+        .collect();
+
+    let common = group.split_first().and_then(|(first, rest)| {
+        rest.iter()
+            .try_fold(*first, |common, assignment| common.intersection(assignment))
+    });
+
+    GroupReport {
+        includes: Intersection::Includes.holds(group),
+        overlaps: Intersection::Overlap.holds(group),
+        common,
+        conflicts,
+    }
+}
+
+pub fn analyze_groups(input: &str) -> Vec<GroupReport> {
+    prepare_input(input).iter().map(analyze_group).collect()
+}
+
 pub fn part_1(input: &str) -> usize {
     check_for_intersection(prepare_input(input), Intersection::Includes)
 }
@@ -68,7 +140,8 @@ pub fn part_2(input: &str) -> usize {
 
 #[cfg(test)]
 mod test {
-    use crate::day4::{part_1, part_2, prepare_input};
+    use crate::day4::range::SectionRange;
+    use crate::day4::{analyze_groups, part_1, part_2, prepare_input, GroupReport};
 
     const FILE_INPUT: &str = include_str!("input.txt");
     const EXAMPLE_INPUT_PART_1: &str = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8";
@@ -86,10 +159,41 @@ mod test {
         assert_eq!(part_2(FILE_INPUT), 952);
     }
 
+    #[test]
+    fn test_group_sizes() {
+        let reports = analyze_groups("2-8,3-4,6-7\n1-5,3-9,4-6\n1-2,4-5,7-8,2-4\n3-3");
+
+        assert_eq!(
+            reports[0],
+            GroupReport {
+                includes: true,
+                overlaps: true,
+                common: None,
+                conflicts: vec![(1, 2), (1, 3)],
+            }
+        );
+        assert_eq!(reports[1].common, Some(SectionRange::new(4, 5)));
+        assert_eq!(reports[1].conflicts, [(1, 2), (1, 3), (2, 3)]);
+        assert!(!reports[2].includes);
+        assert_eq!(reports[2].conflicts, [(1, 4), (2, 4)]);
+        assert_eq!(
+            reports[3],
+            GroupReport {
+                includes: false,
+                overlaps: false,
+                common: Some(SectionRange::new(3, 3)),
+                conflicts: vec![],
+            }
+        );
+
+        assert_eq!(part_1("2-8,3-4,6-7\n1-2,4-5,7-8"), 1);
+        assert_eq!(part_2("2-8,3-4,6-7\n1-2,4-5,7-8"), 1);
+    }
+
     #[test]
     fn test_prepare_input() {
         let result = prepare_input(EXAMPLE_INPUT_PART_1);
-        let expected = vec![
+        let expected = [
             vec![(2, 4), (6, 8)],
             vec![(2, 3), (4, 5)],
             vec![(5, 7), (7, 9)],
@@ -100,7 +204,11 @@ mod test {
 
         assert_eq!(expected.len(), 6);
         for (result, expected) in result.iter().zip(expected.iter()) {
-            assert_eq!(result, expected)
+            let expected = expected
+                .iter()
+                .map(|assignment| SectionRange::from(*assignment))
+                .collect::<Vec<_>>();
+            assert_eq!(result, &expected)
         }
     }
 }
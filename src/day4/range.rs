@@ -0,0 +1,198 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// An inclusive range of section ids such as `2-4`. The start never lies after the end, so a
+/// reversed range like `4-2` covers the same sections as `2-4`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct SectionRange {
+    start: i32,
+    end: i32,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum ParseSectionRangeError {
+    /// A range without a `-` between its two ids.
+    MissingSeparator(String),
+    InvalidId(String),
+}
+
+impl Display for ParseSectionRangeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingSeparator(range) => write!(f, "{range:?} is not of the form start-end"),
+            Self::InvalidId(id) => write!(f, "{id:?} is not a section id"),
+        }
+    }
+}
+
+impl SectionRange {
+    pub fn new(start: i32, end: i32) -> Self {
+        Self {
+            start: start.min(end),
+            end: start.max(end),
+        }
+    }
+
+    pub fn start(&self) -> i32 {
+        self.start
+    }
+
+    pub fn end(&self) -> i32 {
+        self.end
+    }
+
+    /// The number of sections in the range, which is never zero.
+    pub fn len(&self) -> u64 {
+        (self.end as i64 - self.start as i64) as u64 + 1
+    }
+
+    pub fn contains(&self, section: i32) -> bool {
+        self.start <= section && section <= self.end
+    }
+
+    /// Whether every section of `other` is also part of this range.
+    pub fn includes(&self, other: &Self) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        self.overlaps(other).then(|| Self {
+            start: self.start.max(other.start),
+            end: self.end.min(other.end),
+        })
+    }
+
+    /// The range covering both ranges, if they overlap or one starts right after the other ends.
+    pub fn union_if_adjacent(&self, other: &Self) -> Option<Self> {
+        let touches =
+            self.start as i64 <= other.end as i64 + 1 && other.start as i64 <= self.end as i64 + 1;
+
+        touches.then(|| Self {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        })
+    }
+
+    /// The sections of this range that are not part of `other`, as up to two ranges.
+    pub fn subtract(&self, other: &Self) -> Vec<Self> {
+        let Some(overlap) = self.intersection(other) else {
+            return vec![*self];
+        };
+
+        let mut remainder = Vec::new();
+        if self.start < overlap.start {
+            remainder.push(Self::new(self.start, overlap.start - 1));
+        }
+        if overlap.end < self.end {
+            remainder.push(Self::new(overlap.end + 1, self.end));
+        }
+
+        remainder
+    }
+}
+
+impl From<(i32, i32)> for SectionRange {
+    fn from((start, end): (i32, i32)) -> Self {
+        Self::new(start, end)
+    }
+}
+
+impl FromStr for SectionRange {
+    type Err = ParseSectionRangeError;
+
+    fn from_str(range: &str) -> Result<Self, Self::Err> {
+        let range = range.trim();
+        // Skip the first character so a negative start id is not mistaken for the separator.
+        let separator = range
+            .char_indices()
+            .skip(1)
+            .find(|(_, c)| *c == '-')
+            .map(|(index, _)| index)
+            .ok_or_else(|| ParseSectionRangeError::MissingSeparator(range.to_string()))?;
+        let id = |id: &str| {
+            id.trim()
+                .parse::<i32>()
+                .map_err(|_| ParseSectionRangeError::InvalidId(id.to_string()))
+        };
+
+        Ok(Self::new(
+            id(&range[..separator])?,
+            id(&range[separator + 1..])?,
+        ))
+    }
+}
+
+impl Display for SectionRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::day4::range::{ParseSectionRangeError, SectionRange};
+
+    fn range(start: i32, end: i32) -> SectionRange {
+        SectionRange::new(start, end)
+    }
+
+    #[test]
+    fn test_normalization() {
+        assert_eq!(range(8, 2), range(2, 8));
+        assert_eq!("8-2".parse(), Ok(range(2, 8)));
+        assert_eq!("-3--1".parse(), Ok(range(-3, -1)));
+        assert_eq!(range(2, 8).len(), 7);
+        assert_eq!(range(i32::MIN, i32::MAX).len(), 1 << 32);
+        assert_eq!(range(4, 6).to_string(), "4-6");
+        assert_eq!(
+            "4".parse::<SectionRange>(),
+            Err(ParseSectionRangeError::MissingSeparator("4".to_string()))
+        );
+        assert_eq!(
+            "4-x".parse::<SectionRange>(),
+            Err(ParseSectionRangeError::InvalidId("x".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_relations() {
+        assert!(range(2, 8).contains(2));
+        assert!(!range(2, 8).contains(9));
+        assert!(range(2, 8).includes(&range(3, 7)));
+        assert!(!range(3, 7).includes(&range(2, 8)));
+        assert!(range(5, 7).overlaps(&range(7, 9)));
+        assert!(!range(2, 4).overlaps(&range(6, 8)));
+    }
+
+    #[test]
+    fn test_algebra() {
+        assert_eq!(range(2, 6).intersection(&range(4, 8)), Some(range(4, 6)));
+        assert_eq!(range(2, 3).intersection(&range(4, 5)), None);
+
+        assert_eq!(
+            range(2, 3).union_if_adjacent(&range(4, 5)),
+            Some(range(2, 5))
+        );
+        assert_eq!(
+            range(2, 6).union_if_adjacent(&range(4, 8)),
+            Some(range(2, 8))
+        );
+        assert_eq!(range(2, 3).union_if_adjacent(&range(5, 5)), None);
+        assert_eq!(
+            range(i32::MAX, i32::MAX).union_if_adjacent(&range(0, 0)),
+            None
+        );
+
+        assert_eq!(
+            range(2, 8).subtract(&range(4, 5)),
+            [range(2, 3), range(6, 8)]
+        );
+        assert_eq!(range(2, 8).subtract(&range(1, 5)), [range(6, 8)]);
+        assert_eq!(range(2, 8).subtract(&range(0, 9)), []);
+        assert_eq!(range(2, 4).subtract(&range(6, 8)), [range(2, 4)]);
+    }
+}
@@ -0,0 +1,182 @@
+use std::cmp::Reverse;
+use std::fmt::{Display, Formatter};
+
+use crate::day4::Elf;
+
+/// Which elves keep their assignment so that no two remaining assignments overlap, and which
+/// elves have to be moved elsewhere.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Schedule {
+    pub kept: Vec<Elf>,
+    pub moved: Vec<Elf>,
+    /// The summed cost of moving the elves.
+    pub cost: u64,
+}
+
+impl Schedule {
+    fn new(elves: &[Elf], keep: &[bool], cost: impl Fn(&Elf) -> u64) -> Self {
+        let (kept, moved): (Vec<_>, Vec<_>) = elves.iter().zip(keep).partition(|(_, keep)| **keep);
+        let moved = moved.into_iter().map(|(elf, _)| *elf).collect::<Vec<_>>();
+
+        Self {
+            kept: kept.into_iter().map(|(elf, _)| *elf).collect(),
+            cost: moved.iter().map(cost).sum(),
+            moved,
+        }
+    }
+}
+
+/// The elves sorted by the end of their assignment, as indices into `elves`.
+fn by_end(elves: &[Elf]) -> Vec<usize> {
+    let mut order = (0..elves.len()).collect::<Vec<_>>();
+    order.sort_by_key(|index| {
+        let assignment = elves[*index].assignment;
+        (assignment.end(), Reverse(assignment.start()))
+    });
+    order
+}
+
+/// Moves as few elves as possible. Greedily keeping the assignment that ends first leaves the most
+/// room for the others, which is optimal for interval scheduling.
+pub fn minimal_removal(elves: &[Elf]) -> Schedule {
+    let mut keep = vec![false; elves.len()];
+    let mut last_end = None;
+
+    for index in by_end(elves) {
+        let assignment = elves[index].assignment;
+        if last_end.is_none_or(|end| end < assignment.start()) {
+            keep[index] = true;
+            last_end = Some(assignment.end());
+        }
+    }
+
+    Schedule::new(elves, &keep, |_| 1)
+}
+
+/// Moves the elves with the lowest total cost, by keeping the disjoint assignments with the
+/// highest total cost.
+pub fn weighted_removal(elves: &[Elf], cost: impl Fn(&Elf) -> u64) -> Schedule {
+    let order = by_end(elves);
+
+    // best[i] is the highest cost that can be kept among the first i elves of `order`, and the
+    // elves before compatible[i] are the ones that end before the i-th elf starts.
+    let compatible = order
+        .iter()
+        .map(|index| {
+            let start = elves[*index].assignment.start();
+            order.partition_point(|other| elves[*other].assignment.end() < start)
+        })
+        .collect::<Vec<_>>();
+    let mut best = vec![0; order.len() + 1];
+    for (position, index) in order.iter().enumerate() {
+        best[position + 1] = best[position].max(best[compatible[position]] + cost(&elves[*index]));
+    }
+
+    let mut keep = vec![false; elves.len()];
+    let mut position = order.len();
+    while position > 0 {
+        if best[position] == best[position - 1] {
+            position -= 1;
+        } else {
+            keep[order[position - 1]] = true;
+            position = compatible[position - 1];
+        }
+    }
+
+    Schedule::new(elves, &keep, cost)
+}
+
+impl Display for Schedule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (label, elves) in [("keep", &self.kept), ("move", &self.moved)] {
+            for elf in elves {
+                writeln!(
+                    f,
+                    "{label}  line {:>4}, elf {}: {}",
+                    elf.line, elf.position, elf.assignment
+                )?;
+            }
+        }
+
+        writeln!(
+            f,
+            "\n{} kept, {} moved at a cost of {}",
+            self.kept.len(),
+            self.moved.len(),
+            self.cost
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::day4::schedule::{minimal_removal, weighted_removal};
+    use crate::day4::{elves, prepare_input, Elf};
+
+    const EXAMPLE_INPUT: &str = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8";
+    const FILE_INPUT: &str = include_str!("input.txt");
+
+    fn assert_disjoint(kept: &[Elf]) {
+        for (index, a) in kept.iter().enumerate() {
+            for b in &kept[index + 1..] {
+                assert!(
+                    !a.assignment.overlaps(&b.assignment),
+                    "{a:?} overlaps {b:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_minimal_removal() {
+        let elves = elves(&prepare_input(EXAMPLE_INPUT));
+        let schedule = minimal_removal(&elves);
+
+        assert_disjoint(&schedule.kept);
+        assert_eq!(
+            schedule
+                .kept
+                .iter()
+                .map(|elf| elf.assignment.to_string())
+                .collect::<Vec<_>>(),
+            ["2-3", "4-5", "7-9", "6-6"]
+        );
+        assert_eq!(schedule.moved.len(), 8);
+        assert_eq!(schedule.cost, 8);
+    }
+
+    #[test]
+    fn test_weighted_removal() {
+        let elves = elves(&prepare_input("1-10,1-2\n3-4,5-6\n"));
+
+        // Moving the wide assignment is cheapest when every elf costs the same.
+        let schedule = weighted_removal(&elves, |_| 1);
+        assert_eq!(schedule.moved, [elves[0]]);
+        assert_eq!(schedule.cost, 1);
+
+        // An expensive elf keeps their assignment and everybody else moves.
+        let schedule = weighted_removal(&elves, |elf| {
+            if elf.line == 1 && elf.position == 1 {
+                10
+            } else {
+                1
+            }
+        });
+        assert_eq!(schedule.kept, [elves[0]]);
+        assert_eq!(schedule.cost, 3);
+        assert!(schedule
+            .to_string()
+            .ends_with("1 kept, 3 moved at a cost of 3\n"));
+    }
+
+    #[test]
+    fn test_weighted_matches_greedy() {
+        let elves = elves(&prepare_input(FILE_INPUT));
+        let greedy = minimal_removal(&elves);
+        let weighted = weighted_removal(&elves, |_| 1);
+
+        assert_disjoint(&weighted.kept);
+        assert_eq!(greedy.kept.len(), weighted.kept.len());
+        assert_eq!(greedy.cost, weighted.cost);
+    }
+}
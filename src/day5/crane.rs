@@ -0,0 +1,161 @@
+/// How a crane sets down the crates of a single move. Crates are given bottom to top as they were
+/// on the old stack and returned bottom to top as they end up on the new one.
+pub trait CraneModel {
+    fn name(&self) -> String;
+
+    fn set_down(&self, crates: Vec<char>) -> Vec<char>;
+}
+
+/// Lifts one crate at a time, which reverses their order.
+pub struct CrateMover9000;
+
+impl CraneModel for CrateMover9000 {
+    fn name(&self) -> String {
+        "CrateMover 9000".to_string()
+    }
+
+    fn set_down(&self, mut crates: Vec<char>) -> Vec<char> {
+        crates.reverse();
+        crates
+    }
+}
+
+/// Lifts all crates at once, which keeps their order.
+pub struct CrateMover9001;
+
+impl CraneModel for CrateMover9001 {
+    fn name(&self) -> String {
+        "CrateMover 9001".to_string()
+    }
+
+    fn set_down(&self, crates: Vec<char>) -> Vec<char> {
+        crates
+    }
+}
+
+/// Lifts `n` crates at a time from the top. Only the last lift of a move may hold fewer.
+pub struct Chunked(pub usize);
+
+impl CraneModel for Chunked {
+    fn name(&self) -> String {
+        format!("chunked-{}", self.0)
+    }
+
+    fn set_down(&self, crates: Vec<char>) -> Vec<char> {
+        crates.rchunks(self.0.max(1)).flatten().copied().collect()
+    }
+}
+
+/// Lifts at most `n` crates at a time, splitting a move into the fewest lifts of nearly equal
+/// size. Upper lifts take the extra crate when the move does not split evenly.
+pub struct Capacity(pub usize);
+
+impl CraneModel for Capacity {
+    fn name(&self) -> String {
+        format!("capacity-{}", self.0)
+    }
+
+    fn set_down(&self, crates: Vec<char>) -> Vec<char> {
+        let lifts = crates.len().div_ceil(self.0.max(1));
+        let mut set_down = Vec::with_capacity(crates.len());
+        let mut end = crates.len();
+
+        for lift in 0..lifts {
+            let size = end.div_ceil(lifts - lift);
+            set_down.extend_from_slice(&crates[end - size..end]);
+            end -= size;
+        }
+
+        set_down
+    }
+}
+
+/// Splits every move between two arms that each lift a block of crates at once. The arm holding
+/// the upper block sets it down first, so the two blocks trade places.
+pub struct TwoArm;
+
+impl CraneModel for TwoArm {
+    fn name(&self) -> String {
+        "two-arm".to_string()
+    }
+
+    fn set_down(&self, mut crates: Vec<char>) -> Vec<char> {
+        let upper = crates.split_off(crates.len() / 2);
+        [upper, crates].concat()
+    }
+}
+
+/// Looks up a crane by its name, such as `CrateMover 9000`, `chunked-3`, `capacity-4` or
+/// `two-arm`.
+pub fn crane_model(name: &str) -> Option<Box<dyn CraneModel>> {
+    let sized = |prefix: &str| {
+        name.strip_prefix(prefix)
+            .and_then(|size| size.parse::<usize>().ok())
+            .filter(|size| *size > 0)
+    };
+
+    match name {
+        "CrateMover 9000" => Some(Box::new(CrateMover9000)),
+        "CrateMover 9001" => Some(Box::new(CrateMover9001)),
+        "two-arm" => Some(Box::new(TwoArm)),
+        _ => sized("chunked-")
+            .map(|size| Box::new(Chunked(size)) as Box<dyn CraneModel>)
+            .or_else(|| sized("capacity-").map(|size| Box::new(Capacity(size)) as _)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::day5::crane::{crane_model, Capacity, Chunked, CraneModel, TwoArm};
+    use crate::day5::execute;
+
+    const EXAMPLE_INPUT: &str = "    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2";
+
+    fn set_down(crane: &impl CraneModel, crates: &str) -> String {
+        String::from_iter(crane.set_down(crates.chars().collect()))
+    }
+
+    #[test]
+    fn test_set_down() {
+        assert_eq!(set_down(&Chunked(2), "abcdefg"), "fgdebca");
+        assert_eq!(set_down(&Chunked(1), "abc"), "cba");
+        assert_eq!(set_down(&Capacity(3), "abcdefg"), "efgcdab");
+        assert_eq!(set_down(&Capacity(3), "abcdef"), "defabc");
+        assert_eq!(set_down(&TwoArm, "abcde"), "cdeab");
+        assert_eq!(set_down(&TwoArm, "a"), "a");
+    }
+
+    #[test]
+    fn test_crane_model() {
+        for name in [
+            "CrateMover 9000",
+            "CrateMover 9001",
+            "chunked-2",
+            "capacity-5",
+            "two-arm",
+        ] {
+            assert_eq!(crane_model(name).unwrap().name(), name);
+        }
+        assert!(crane_model("chunked-0").is_none());
+        assert!(crane_model("CrateMover 9002").is_none());
+
+        let chunked = crane_model("chunked-2").unwrap();
+        let two_arm = crane_model("two-arm").unwrap();
+        assert_eq!(
+            execute(EXAMPLE_INPUT, chunked.as_ref()),
+            Ok("MCZ".to_string())
+        );
+        assert_eq!(
+            execute(EXAMPLE_INPUT, two_arm.as_ref()),
+            Ok("CMZ".to_string())
+        );
+    }
+}
@@ -1,56 +1,120 @@
-struct Stack {
-    pub id: u8,
+use std::fmt::{Display, Formatter};
+
+use crane::{crane_model, CraneModel};
+
+pub mod crane;
+
+/// A stack of crates, stored bottom to top so the top crate is the last one.
+pub struct Stack {
     pub crates: Vec<char>,
 }
 
 impl Stack {
-    fn push(&mut self, crate_: char) {
-        let mut crates = [crate_].to_vec();
-        crates.append(&mut self.crates);
+    fn top(&self) -> Option<char> {
+        self.crates.last().copied()
+    }
 
-        self.crates = crates;
+    /// Lifts the top `count` crates off the stack at once, keeping their order. Lifting more crates
+    /// than the stack holds empties it.
+    fn take(&mut self, count: usize) -> Vec<char> {
+        self.crates
+            .split_off(self.crates.len().saturating_sub(count))
     }
 
-    fn pop(&mut self) -> Option<char> {
-        if let Some(pos) = self
-            .crates
-            .iter()
-            .position(|x| x == self.crates.first().unwrap())
-        {
-            let first = self.crates[0];
-            self.crates.remove(0);
-            return Some(first);
-        }
+    /// Sets crates down on top of the stack, the first one lowest.
+    fn put(&mut self, crates: impl IntoIterator<Item = char>) {
+        self.crates.extend(crates);
+    }
+}
+
+/// Stands in for the top crate of a stack that ended up empty.
+pub const EMPTY_STACK: char = '_';
 
-        None
+#[derive(Clone, PartialEq, Debug)]
+pub enum SimulationError {
+    /// A stack number outside of `1..=stacks`.
+    UnknownStack {
+        instruction: usize,
+        stack: usize,
+        stacks: usize,
+    },
+    /// A move of more crates than the stack holds.
+    NotEnoughCrates {
+        instruction: usize,
+        stack: usize,
+        requested: usize,
+        available: usize,
+    },
+    /// A line of the procedure that is not of the form `move N from A to B`.
+    InvalidInstruction { instruction: usize, content: String },
+    /// Input without the blank line that separates the drawing of the stacks from the procedure.
+    MissingInstructions,
+}
+
+impl Display for SimulationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownStack {
+                instruction,
+                stack,
+                stacks,
+            } => write!(
+                f,
+                "instruction {instruction}: there is no stack {stack} among {stacks} stacks"
+            ),
+            Self::NotEnoughCrates {
+                instruction,
+                stack,
+                requested,
+                available,
+            } => write!(
+                f,
+                "instruction {instruction}: cannot move {requested} crates off stack {stack} with {available}"
+            ),
+            Self::InvalidInstruction {
+                instruction,
+                content,
+            } => write!(f, "instruction {instruction}: cannot parse {content:?}"),
+            Self::MissingInstructions => write!(f, "the input contains no rearrangement procedure"),
+        }
     }
 }
 
 struct Instruction {
-    pub count: u8,
-    pub from: u8,
-    pub to: u8,
+    pub count: usize,
+    pub from: usize,
+    pub to: usize,
 }
 
-impl From<&str> for Instruction {
-    fn from(string: &str) -> Self {
-        let split = string.split("from").map(str::trim).collect::<Vec<_>>();
-        let from_to = split[1].split("to").map(str::trim).collect::<Vec<_>>();
+impl TryFrom<&str> for Instruction {
+    type Error = ();
 
-        Self {
-            count: split[0].replace("move ", "").parse::<u8>().unwrap(),
-            from: from_to[0].parse::<u8>().unwrap(),
-            to: from_to[1].replace("to ", "").trim().parse::<u8>().unwrap(),
-        }
+    fn try_from(string: &str) -> Result<Self, Self::Error> {
+        let words = string.split_whitespace().collect::<Vec<_>>();
+        let ["move", count, "from", from, "to", to] = words[..] else {
+            return Err(());
+        };
+        let number = |word: &str| word.parse::<usize>().map_err(|_| ());
+
+        Ok(Self {
+            count: number(count)?,
+            from: number(from)?,
+            to: number(to)?,
+        })
     }
 }
 
 fn construct_stacks(input: Vec<Vec<String>>) -> Vec<Stack> {
-    (0..input[0].len())
+    let width = input.iter().map(Vec::len).max().unwrap_or(0);
+
+    // Rows are listed top to bottom, and rows may end early when their last stacks are lower.
+    (0..width)
         .map(|x| Stack {
-            id: x as u8,
-            crates: (0..input.len())
-                .map(|y| input[y][x].chars().into_iter().collect::<Vec<_>>()[1])
+            crates: input
+                .iter()
+                .rev()
+                .filter_map(|row| row.get(x))
+                .filter_map(|cell| cell.chars().nth(1))
                 .filter(|crate_| !crate_.is_whitespace())
                 .collect(),
         })
@@ -60,7 +124,7 @@ fn construct_stacks(input: Vec<Vec<String>>) -> Vec<Stack> {
 fn parse_crates(input: &str) -> Vec<Vec<String>> {
     input
         .split('\n')
-        .take(input.split('\n').into_iter().count() - 1)
+        .take(input.split('\n').count() - 1)
         .map(|row| {
             row.chars()
                 .collect::<Vec<_>>()
@@ -78,53 +142,81 @@ fn parse_crates(input: &str) -> Vec<Vec<String>> {
         .collect()
 }
 
-fn parse_instructions(input: &str) -> Vec<Instruction> {
-    input.trim().split('\n').map(Instruction::from).collect()
+fn parse_instructions(input: &str) -> Result<Vec<Instruction>, SimulationError> {
+    input
+        .trim()
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            Instruction::try_from(line).map_err(|_| SimulationError::InvalidInstruction {
+                instruction: index + 1,
+                content: line.to_string(),
+            })
+        })
+        .collect()
 }
 
-fn prepare_input(input: &str) -> (Vec<Stack>, Vec<Instruction>) {
-    let parts = input
-        .split("\n\n")
-        .filter(|part| !part.is_empty())
-        .collect::<Vec<_>>();
+fn prepare_input(input: &str) -> Result<(Vec<Stack>, Vec<Instruction>), SimulationError> {
+    let (crates, instructions) = input
+        .split_once("\n\n")
+        .ok_or(SimulationError::MissingInstructions)?;
 
-    (
-        construct_stacks(parse_crates(parts[0])),
-        parse_instructions(parts[1]),
-    )
+    Ok((
+        construct_stacks(parse_crates(crates)),
+        parse_instructions(instructions)?,
+    ))
 }
 
-fn execute(input: &str, part: u8) -> String {
-    let (mut stacks, instructions) = prepare_input(input);
+/// Runs every instruction, failing at the first one that cannot be parsed, refers to a missing
+/// stack or moves more crates than there are. Instructions are counted from one.
+pub fn execute(input: &str, crane: &dyn CraneModel) -> Result<String, SimulationError> {
+    let (mut stacks, instructions) = prepare_input(input)?;
 
-    for instruction in instructions {
-        let mut popped_chars: Vec<char> = (0..instruction.count)
-            .filter_map(|_| stacks[instruction.from as usize - 1].pop())
-            .collect();
+    for (index, instruction) in instructions.iter().enumerate() {
+        let stack_index = |stack: usize| {
+            (1..=stacks.len())
+                .contains(&stack)
+                .then(|| stack - 1)
+                .ok_or(SimulationError::UnknownStack {
+                    instruction: index + 1,
+                    stack,
+                    stacks: stacks.len(),
+                })
+        };
+        let (from, to) = (stack_index(instruction.from)?, stack_index(instruction.to)?);
 
-        if part == 1 {
-            popped_chars.reverse();
+        let available = stacks[from].crates.len();
+        if instruction.count > available {
+            return Err(SimulationError::NotEnoughCrates {
+                instruction: index + 1,
+                stack: instruction.from,
+                requested: instruction.count,
+                available,
+            });
         }
 
-        let to_stack = &mut stacks[instruction.to as usize - 1];
-        popped_chars.append(&mut to_stack.crates);
-        to_stack.crates = popped_chars;
+        let lifted = stacks[from].take(instruction.count);
+        stacks[to].put(crane.set_down(lifted));
     }
 
-    String::from_iter(stacks.iter().map(|stack| stack.crates.first().unwrap()))
+    Ok(String::from_iter(
+        stacks
+            .iter()
+            .map(|stack| stack.top().unwrap_or(EMPTY_STACK)),
+    ))
 }
 
-pub fn part_1(input: &str) -> String {
-    execute(input, 1)
+pub fn part_1(input: &str) -> Result<String, SimulationError> {
+    execute(input, crane_model("CrateMover 9000").unwrap().as_ref())
 }
 
-pub fn part_2(input: &str) -> String {
-    execute(input, 2)
+pub fn part_2(input: &str) -> Result<String, SimulationError> {
+    execute(input, crane_model("CrateMover 9001").unwrap().as_ref())
 }
 
 #[cfg(test)]
 mod test {
-    use crate::day5::{part_1, part_2};
+    use crate::day5::{part_1, part_2, SimulationError};
 
     const FILE_INPUT: &str = include_str!("input.txt");
     const EXAMPLE_INPUT: &str = "    [D]    
@@ -139,13 +231,105 @@ move 1 from 1 to 2";
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(EXAMPLE_INPUT), "CMZ");
-        assert_eq!(part_1(FILE_INPUT), "MQTPGLLDN");
+        assert_eq!(part_1(EXAMPLE_INPUT), Ok("CMZ".to_string()));
+        assert_eq!(part_1(FILE_INPUT), Ok("MQTPGLLDN".to_string()));
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(EXAMPLE_INPUT), "MCD");
-        assert_eq!(part_2(FILE_INPUT), "LVZPSTTCZ");
+        assert_eq!(part_2(EXAMPLE_INPUT), Ok("MCD".to_string()));
+        assert_eq!(part_2(FILE_INPUT), Ok("LVZPSTTCZ".to_string()));
+    }
+
+    #[test]
+    fn test_large_input() {
+        let stacks = 300;
+        let mut input = (0..1000)
+            .map(|row| {
+                let crate_ = format!("[{}] ", (b'A' + (row % 26) as u8) as char);
+                crate_.repeat(stacks).trim_end().to_string() + "\n"
+            })
+            .collect::<String>();
+        input += &(1..=stacks)
+            .map(|id| format!(" {id:<3}"))
+            .collect::<String>();
+        input += "\n\n";
+        for round in 0..20_000 {
+            let (from, to) = (round % stacks + 1, (round + 7) % stacks + 1);
+            input += &format!("move 999 from {from} to {to}\nmove 999 from {to} to {from}\n");
+        }
+
+        let expected = "A".repeat(stacks);
+        assert_eq!(part_1(&input), Ok(expected.clone()));
+        assert_eq!(part_2(&input), Ok(expected));
+    }
+
+    #[test]
+    fn test_errors() {
+        let stacks = "[A]    \n[B] [C]\n 1   2 \n\n";
+
+        assert_eq!(
+            part_1(&format!("{stacks}move 2 from 1 to 2\nmove 1 from 3 to 1")),
+            Err(SimulationError::UnknownStack {
+                instruction: 2,
+                stack: 3,
+                stacks: 2
+            })
+        );
+        assert_eq!(
+            part_2(&format!("{stacks}move 1 from 1 to 0")),
+            Err(SimulationError::UnknownStack {
+                instruction: 1,
+                stack: 0,
+                stacks: 2
+            })
+        );
+        assert_eq!(
+            part_1(&format!("{stacks}move 1 from 2 to 1\nmove 4 from 1 to 2")),
+            Err(SimulationError::NotEnoughCrates {
+                instruction: 2,
+                stack: 1,
+                requested: 4,
+                available: 3
+            })
+        );
+        assert_eq!(
+            part_2(&format!("{stacks}move 1 from 2 to 1")),
+            Ok("C_".to_string())
+        );
+    }
+
+    #[test]
+    fn test_invalid_instructions() {
+        let stacks = "[A]    \n[B] [C]\n 1   2 \n\n";
+        let invalid = |instruction: usize, content: &str| {
+            Err(SimulationError::InvalidInstruction {
+                instruction,
+                content: content.to_string(),
+            })
+        };
+
+        for content in [
+            "move x from 1 to 2",
+            "move 1 from 1",
+            "move 1 to 2",
+            "move -1 from 1 to 2",
+            "move 1 from 1 to 2 and 3",
+        ] {
+            assert_eq!(
+                part_1(&format!("{stacks}move 1 from 1 to 2\n{content}")),
+                invalid(2, content)
+            );
+        }
+        assert_eq!(
+            part_2(&format!("{stacks}move 1 from 1 to 2\n\nmove 1 from 2 to 1")),
+            invalid(2, "")
+        );
+        assert_eq!(part_2(stacks), Ok("AC".to_string()));
+        assert_eq!(
+            part_1(stacks.trim_end()),
+            Err(SimulationError::MissingInstructions)
+        );
+        assert_eq!(part_1(""), Err(SimulationError::MissingInstructions));
     }
 }
@@ -1,10 +1,147 @@
 #![allow(dead_code)]
 #![allow(unused_variables)]
 
+use std::collections::HashMap;
+use std::process::ExitCode;
+
 mod day1;
 mod day2;
+mod day24;
+mod day25;
 mod day3;
 mod day4;
 mod day5;
 
-fn main() {}
+const USAGE: &str = "\
+usage:
+    aoc-2022 day1 stats [--format=table|json] [FILE]
+    aoc-2022 day2 score [--part=1|2] [--encoding=FILE] [--explain] [FILE]
+    aoc-2022 day2 tournament [--csv=FILE] PATH...";
+
+/// Command line arguments after the subcommand: `--key=value` options, `--flag` switches and
+/// positional paths.
+struct Arguments<'a> {
+    pub options: HashMap<&'a str, &'a str>,
+    pub flags: Vec<&'a str>,
+    pub paths: Vec<&'a str>,
+}
+
+impl<'a> From<&[&'a str]> for Arguments<'a> {
+    fn from(arguments: &[&'a str]) -> Self {
+        let mut parsed = Self {
+            options: HashMap::new(),
+            flags: Vec::new(),
+            paths: Vec::new(),
+        };
+
+        for argument in arguments {
+            match argument.strip_prefix("--") {
+                Some(option) => match option.split_once('=') {
+                    Some((key, value)) => {
+                        parsed.options.insert(key, value);
+                    }
+                    None => parsed.flags.push(option),
+                },
+                None => parsed.paths.push(argument),
+            }
+        }
+
+        parsed
+    }
+}
+
+impl Arguments<'_> {
+    /// Reads the single input file, or falls back to the puzzle input bundled with the day.
+    fn read_input(&self, bundled: &str) -> Result<String, String> {
+        match self.paths.as_slice() {
+            [] => Ok(bundled.to_string()),
+            [path] => std::fs::read_to_string(path).map_err(|error| format!("{path}: {error}")),
+            _ => Err(format!("expected at most one input file\n{USAGE}")),
+        }
+    }
+}
+
+fn day1_stats(arguments: Arguments<'_>) -> Result<String, String> {
+    let format = arguments
+        .options
+        .get("format")
+        .map_or(Ok(day1::stats::OutputFormat::Table), |format| {
+            format.parse()
+        })?;
+    let input = arguments.read_input(include_str!("day1/input.txt"))?;
+    let blocks = day1::split_string(&input).map_err(|error| error.to_string())?;
+
+    day1::stats::stats(&day1::into_elves(blocks))
+        .map(|stats| stats.render(format))
+        .ok_or_else(|| "the input does not contain any elves".to_string())
+}
+
+fn day2_score(arguments: Arguments<'_>) -> Result<String, String> {
+    let encoding = match arguments.options.get("encoding") {
+        Some(path) => {
+            let config =
+                std::fs::read_to_string(path).map_err(|error| format!("{path}: {error}"))?;
+            day2::encoding::Encoding::from_config(&config)
+                .map_err(|error| format!("{path}: {error}"))?
+        }
+        None => day2::encoding::Encoding::default(),
+    };
+    let input = arguments.read_input(include_str!("day2/input.txt"))?;
+
+    let rounds = match arguments.options.get("part").copied().unwrap_or("1") {
+        "1" => encoding.parse(&input).map_err(|e| e.to_string())?,
+        "2" => day2::explain::resolve_outcomes(encoding.parse(&input).map_err(|e| e.to_string())?)
+            .collect(),
+        part => return Err(format!("unknown part {part:?}\n{USAGE}")),
+    };
+    let reports = day2::explain::round_reports(rounds).collect::<Vec<_>>();
+    let total = reports.last().map_or(0, |report| report.running_total);
+
+    if arguments.flags.contains(&"explain") {
+        Ok(format!(
+            "{}\ntotal {total}\n",
+            day2::explain::render(&reports)
+        ))
+    } else {
+        Ok(format!("{total}\n"))
+    }
+}
+
+fn day2_tournament(arguments: Arguments<'_>) -> Result<String, String> {
+    // Directories expand to any number of guides, so only the loaded guides can be counted.
+    let guides = day2::tournament::load_guides(&arguments.paths).map_err(|e| e.to_string())?;
+    if guides.len() < 2 {
+        return Err(format!("a tournament needs at least two guides\n{USAGE}"));
+    }
+
+    let tournament = day2::tournament::round_robin(guides);
+
+    if let Some(path) = arguments.options.get("csv") {
+        std::fs::write(path, tournament.to_csv()).map_err(|error| format!("{path}: {error}"))?;
+    }
+
+    Ok(tournament.to_string())
+}
+
+fn main() -> ExitCode {
+    let arguments = std::env::args().skip(1).collect::<Vec<_>>();
+    let arguments = arguments.iter().map(String::as_str).collect::<Vec<_>>();
+
+    let result = match arguments.as_slice() {
+        ["day1", "stats", rest @ ..] => day1_stats(Arguments::from(rest)),
+        ["day2", "score", rest @ ..] => day2_score(Arguments::from(rest)),
+        ["day2", "tournament", rest @ ..] => day2_tournament(Arguments::from(rest)),
+        _ => Err(USAGE.to_string()),
+    };
+
+    match result {
+        Ok(output) => {
+            print!("{output}");
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
+}